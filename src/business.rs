@@ -0,0 +1,339 @@
+use crate::times::DayTimes;
+use crate::{
+    days_in_month, dst, end, nth_local, Error, Fold, Gap, HolidayCalendar, Holidays, Recurrent,
+    Times,
+};
+use chrono::{DateTime, Datelike, Months, NaiveDate, NaiveDateTime, TimeZone};
+use std::collections::BTreeSet;
+
+/// Most days a month has, business ones or not depending on the calendar.
+const MAX_NTH: i8 = 31;
+
+/// Months looked through for an occurrence before giving up.
+const MAX_MONTHS: usize = 120;
+
+/// Repeats on the nth business days of every month, e.g. the 3rd one or the last one.
+///
+/// Business days are told apart by a [`HolidayCalendar`], weekdays only by default. Occurrences
+/// are found and bounded like the ones of a [`Recurrence`](crate::Recurrence), every month: there
+/// is no interval. Ends after ten years without any of the days, which only calendars with too
+/// few business days for them have.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BusinessDays<C: HolidayCalendar> {
+    pub(crate) days: BTreeSet<i8>,
+    pub(crate) times: DayTimes,
+    calendar: C,
+    pub(crate) gap: Gap,
+    pub(crate) fold: Fold,
+    start: Option<NaiveDateTime>,
+    until: Option<NaiveDateTime>,
+    count: Option<u32>,
+    /// Wall time of the `count`th occurrence, computed once the bounds are set.
+    last: Option<NaiveDateTime>,
+}
+
+impl BusinessDays<Holidays> {
+    /// Repeats on the `days`th weekdays of every month, negative ones counting from the end: -1
+    /// is the last weekday.
+    pub fn weekdays<S: Times>(days: &[i8], times: S) -> Result<Self, Error> {
+        Self::new(days, times, Holidays::new())
+    }
+}
+
+impl<C: HolidayCalendar> BusinessDays<C> {
+    /// Repeats on the `days`th business days of `calendar` in every month, negative ones
+    /// counting from the end: -1 is the last business day.
+    pub fn new<S: Times>(days: &[i8], times: S, calendar: C) -> Result<Self, Error> {
+        if let Some(nth) = days
+            .iter()
+            .find(|nth| **nth == 0 || !(-MAX_NTH..=MAX_NTH).contains(*nth))
+        {
+            return Err(Error::InvalidNth(*nth));
+        }
+        let times = times.times();
+        if days.is_empty() {
+            Err(Error::Empty)
+        } else if times.is_empty() {
+            Err(Error::NoTime)
+        } else {
+            Ok(BusinessDays {
+                days: days.iter().copied().collect(),
+                times: DayTimes::List(times.into_iter().collect()),
+                calendar,
+                gap: Gap::default(),
+                fold: Fold::default(),
+                start: None,
+                until: None,
+                count: None,
+                last: None,
+            })
+        }
+    }
+
+    /// See [`Recurrence::with_gap`](crate::Recurrence::with_gap).
+    pub fn with_gap(mut self, gap: Gap) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// See [`Recurrence::with_fold`](crate::Recurrence::with_fold).
+    pub fn with_fold(mut self, fold: Fold) -> Self {
+        self.fold = fold;
+        self
+    }
+
+    /// See [`Recurrence::with_start`](crate::Recurrence::with_start).
+    pub fn with_start(mut self, start: NaiveDateTime) -> Self {
+        self.start = Some(start);
+        self.with_last()
+    }
+
+    /// See [`Recurrence::with_until`](crate::Recurrence::with_until).
+    pub fn with_until(mut self, until: NaiveDateTime) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    /// See [`Recurrence::with_count`](crate::Recurrence::with_count).
+    pub fn with_count(mut self, count: u32) -> Result<Self, Error> {
+        if count == 0 {
+            return Err(Error::InvalidCount);
+        }
+        if self.start.is_none() {
+            return Err(Error::NoStart);
+        }
+        self.count = Some(count);
+        Ok(self.with_last())
+    }
+
+    pub fn calendar(&self) -> &C {
+        &self.calendar
+    }
+
+    pub fn start(&self) -> Option<NaiveDateTime> {
+        self.start
+    }
+
+    pub fn until(&self) -> Option<NaiveDateTime> {
+        self.until
+    }
+
+    pub fn count(&self) -> Option<u32> {
+        self.count
+    }
+
+    fn with_last(mut self) -> Self {
+        self.last = nth_local(self.start, self.count, |local| self.next_local(local));
+        self
+    }
+
+    /// Wall time of the last occurrence, if bounded.
+    fn end(&self) -> Option<NaiveDateTime> {
+        end(self.last, self.until)
+    }
+
+    /// The dates the days fall on in the given month.
+    fn resolve(&self, year: i32, month: u32) -> BTreeSet<NaiveDate> {
+        let business: Vec<_> = (1..=days_in_month(year, month))
+            .filter_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+            .filter(|date| self.calendar.is_business_day(*date))
+            .collect();
+        self.days
+            .iter()
+            .filter_map(|nth| {
+                let index = if *nth > 0 {
+                    *nth as usize - 1
+                } else {
+                    business.len().checked_sub(nth.unsigned_abs() as usize)?
+                };
+                business.get(index).copied()
+            })
+            .collect()
+    }
+
+    fn falls_on(&self, date: NaiveDate) -> bool {
+        self.resolve(date.year(), date.month()).contains(&date)
+    }
+
+    fn next_date(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let mut first = date.with_day(1)?;
+        for _ in 0..MAX_MONTHS {
+            let dates = self.resolve(first.year(), first.month());
+            if let Some(next) = dates.into_iter().find(|next| *next > date) {
+                return Some(next);
+            }
+            first = first + Months::new(1);
+        }
+        None
+    }
+
+    fn prev_date(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let mut first = date.with_day(1)?;
+        for _ in 0..MAX_MONTHS {
+            let dates = self.resolve(first.year(), first.month());
+            if let Some(prev) = dates.into_iter().rev().find(|prev| *prev < date) {
+                return Some(prev);
+            }
+            first = first - Months::new(1);
+        }
+        None
+    }
+
+    /// First wall time strictly after `local`.
+    fn next_local(&self, local: NaiveDateTime) -> Option<NaiveDateTime> {
+        let today = local.date();
+        if self.falls_on(today) {
+            if let Some(time) = self.times.after(local.time()) {
+                return Some(today.and_time(time));
+            }
+        }
+        Some(self.next_date(today)?.and_time(self.times.first()))
+    }
+
+    /// Last wall time strictly before `local`.
+    fn prev_local(&self, local: NaiveDateTime) -> Option<NaiveDateTime> {
+        let today = local.date();
+        if self.falls_on(today) {
+            if let Some(time) = self.times.before(local.time()) {
+                return Some(today.and_time(time));
+            }
+        }
+        Some(self.prev_date(today)?.and_time(self.times.last()))
+    }
+}
+
+impl<C: HolidayCalendar> Recurrent for BusinessDays<C> {
+    fn next<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>> {
+        dst::next(date, self.dst_policy(), self.start, self.end(), |local| {
+            self.next_local(local)
+        })
+    }
+
+    fn prev<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>> {
+        dst::prev(date, self.dst_policy(), self.start, self.end(), |local| {
+            self.prev_local(local)
+        })
+    }
+
+    fn dst_policy(&self) -> (Gap, Fold) {
+        (self.gap, self.fold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BusinessDays, Error, Holidays, Recurrent};
+    use chrono::{DateTime, NaiveDate, NaiveTime, Utc, Weekday};
+
+    fn parse(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn noon() -> NaiveTime {
+        NaiveTime::from_hms_opt(12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn weekdays() {
+        let third = BusinessDays::weekdays(&[3], noon()).unwrap();
+        // friday january 1st 2021
+        assert_eq!(
+            third
+                .occurrences_after(&parse("2021-01-01T00:00:00Z"))
+                .take(3)
+                .collect::<Vec<_>>(),
+            vec![
+                parse("2021-01-05T12:00:00Z"),
+                parse("2021-02-03T12:00:00Z"),
+                parse("2021-03-03T12:00:00Z"),
+            ]
+        );
+        let last = BusinessDays::weekdays(&[-1], noon()).unwrap();
+        assert_eq!(
+            last.next(&parse("2021-07-01T00:00:00Z")),
+            Some(parse("2021-07-30T12:00:00Z"))
+        );
+        assert_eq!(
+            last.prev(&parse("2021-07-30T12:00:00Z")),
+            Some(parse("2021-06-30T12:00:00Z"))
+        );
+        assert!(matches!(
+            BusinessDays::weekdays(&[32], noon()),
+            Err(Error::InvalidNth(32))
+        ));
+        assert!(matches!(
+            BusinessDays::weekdays(&[i8::MIN], noon()),
+            Err(Error::InvalidNth(i8::MIN))
+        ));
+    }
+
+    #[test]
+    fn one_day_weekend() {
+        let holidays = Holidays::new().with_weekend(Weekday::Fri);
+        // 26 business days in january 2021, 24 in february, 27 in march
+        let days = BusinessDays::new(&[24, 27], noon(), holidays).unwrap();
+        assert_eq!(
+            days.occurrences_after(&parse("2021-01-01T00:00:00Z"))
+                .take(4)
+                .collect::<Vec<_>>(),
+            vec![
+                parse("2021-01-28T12:00:00Z"),
+                parse("2021-02-28T12:00:00Z"),
+                parse("2021-03-28T12:00:00Z"),
+                parse("2021-03-31T12:00:00Z"),
+            ]
+        );
+    }
+
+    #[test]
+    fn holidays() {
+        let holidays = Holidays::new()
+            .with_fixed(1, 1)
+            .unwrap()
+            .with_date(NaiveDate::from_ymd_opt(2021, 7, 30).unwrap());
+        let days = BusinessDays::new(&[3, -1], noon(), holidays).unwrap();
+        assert_eq!(
+            days.between(
+                &parse("2021-01-01T00:00:00Z"),
+                &parse("2021-02-01T00:00:00Z"),
+                false
+            )
+            .collect::<Vec<_>>(),
+            vec![parse("2021-01-06T12:00:00Z"), parse("2021-01-29T12:00:00Z")]
+        );
+        assert_eq!(
+            days.prev(&parse("2021-08-01T00:00:00Z")),
+            Some(parse("2021-07-29T12:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn bounds() {
+        let wall = |s: &str| parse(s).naive_utc();
+        let first = BusinessDays::weekdays(&[1], noon())
+            .unwrap()
+            .with_start(wall("2021-02-01T12:00:00Z"))
+            .with_count(3)
+            .unwrap();
+        assert_eq!(
+            first
+                .occurrences_after(&parse("2021-01-01T00:00:00Z"))
+                .collect::<Vec<_>>(),
+            vec![
+                parse("2021-02-01T12:00:00Z"),
+                parse("2021-03-01T12:00:00Z"),
+                parse("2021-04-01T12:00:00Z"),
+            ]
+        );
+        assert_eq!(first.prev(&parse("2021-02-01T12:00:00Z")), None);
+        let first = first.with_until(wall("2021-03-15T00:00:00Z"));
+        assert_eq!(
+            first.prev(&parse("2022-01-01T00:00:00Z")),
+            Some(parse("2021-03-01T12:00:00Z"))
+        );
+        assert!(matches!(
+            BusinessDays::weekdays(&[1], noon()).unwrap().with_count(3),
+            Err(Error::NoStart)
+        ));
+    }
+}
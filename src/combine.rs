@@ -0,0 +1,309 @@
+use crate::{Error, Fold, Gap, Recurrent};
+use chrono::{DateTime, Duration, TimeZone};
+
+/// Candidates an [`Intersection`] or a [`Difference`] goes through looking for an occurrence
+/// before giving up, so that they end when their sides never agree again.
+const MAX_CANDIDATES: usize = 10_000;
+
+fn nano() -> Duration {
+    Duration::nanoseconds(1)
+}
+
+/// Whether `recurrent` occurs at `date`.
+fn contains<R: Recurrent, T: TimeZone>(recurrent: &R, date: &DateTime<T>) -> bool {
+    recurrent.next(&(date.clone() - nano())).as_ref() == Some(date)
+}
+
+/// Occurs whenever either side does, see [`Recurrent::union`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Union<A, B> {
+    pub(crate) a: A,
+    pub(crate) b: B,
+}
+
+impl<A, B> Union<A, B> {
+    pub(crate) fn new(a: A, b: B) -> Self {
+        Union { a, b }
+    }
+}
+
+impl<A: Recurrent, B: Recurrent> Recurrent for Union<A, B> {
+    fn next<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>> {
+        match (self.a.next(date), self.b.next(date)) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    fn prev<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>> {
+        match (self.a.prev(date), self.b.prev(date)) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// The first side's, which the second one is expected to share.
+    fn dst_policy(&self) -> (Gap, Fold) {
+        self.a.dst_policy()
+    }
+}
+
+/// Occurs whenever both sides do, see [`Recurrent::intersection`].
+///
+/// Gives up once the sides have missed each other 10 000 times in a row, which
+/// [`try_next`](Self::try_next) tells apart from an end.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Intersection<A, B> {
+    pub(crate) a: A,
+    pub(crate) b: B,
+}
+
+impl<A, B> Intersection<A, B> {
+    pub(crate) fn new(a: A, b: B) -> Self {
+        Intersection { a, b }
+    }
+}
+
+impl<A: Recurrent, B: Recurrent> Intersection<A, B> {
+    /// First occurrence strictly after `date`, `None` once a side has ended, and
+    /// [`Error::SearchLimit`] when the sides missed each other too many times in a row.
+    pub fn try_next<T: TimeZone>(&self, date: &DateTime<T>) -> Result<Option<DateTime<T>>, Error> {
+        let Some(mut candidate) = self.a.next(date) else {
+            return Ok(None);
+        };
+        for _ in 0..MAX_CANDIDATES {
+            // leapfrog to the first occurrence of each side at or after the other one
+            let Some(other) = self.b.next(&(candidate.clone() - nano())) else {
+                return Ok(None);
+            };
+            if other == candidate {
+                return Ok(Some(candidate));
+            }
+            let Some(next) = self.a.next(&(other - nano())) else {
+                return Ok(None);
+            };
+            candidate = next;
+        }
+        Err(Error::SearchLimit)
+    }
+
+    /// Last occurrence strictly before `date`, see [`try_next`](Self::try_next).
+    pub fn try_prev<T: TimeZone>(&self, date: &DateTime<T>) -> Result<Option<DateTime<T>>, Error> {
+        let Some(mut candidate) = self.a.prev(date) else {
+            return Ok(None);
+        };
+        for _ in 0..MAX_CANDIDATES {
+            let Some(other) = self.b.prev(&(candidate.clone() + nano())) else {
+                return Ok(None);
+            };
+            if other == candidate {
+                return Ok(Some(candidate));
+            }
+            let Some(prev) = self.a.prev(&(other + nano())) else {
+                return Ok(None);
+            };
+            candidate = prev;
+        }
+        Err(Error::SearchLimit)
+    }
+}
+
+impl<A: Recurrent, B: Recurrent> Recurrent for Intersection<A, B> {
+    /// See [`try_next`](Intersection::try_next), giving up being an end.
+    fn next<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>> {
+        self.try_next(date).ok().flatten()
+    }
+
+    fn prev<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>> {
+        self.try_prev(date).ok().flatten()
+    }
+
+    /// The first side's, which the second one is expected to share.
+    fn dst_policy(&self) -> (Gap, Fold) {
+        self.a.dst_policy()
+    }
+}
+
+/// Occurs whenever the first side does but the second one doesn't, see
+/// [`Recurrent::difference`].
+///
+/// Gives up once the second side has matched the first one 10 000 times in a row, which
+/// [`try_next`](Self::try_next) tells apart from an end.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Difference<A, B> {
+    pub(crate) a: A,
+    pub(crate) b: B,
+}
+
+impl<A, B> Difference<A, B> {
+    pub(crate) fn new(a: A, b: B) -> Self {
+        Difference { a, b }
+    }
+}
+
+impl<A: Recurrent, B: Recurrent> Difference<A, B> {
+    /// First occurrence strictly after `date`, `None` once the first side has ended, and
+    /// [`Error::SearchLimit`] when the second side matched it too many times in a row.
+    pub fn try_next<T: TimeZone>(&self, date: &DateTime<T>) -> Result<Option<DateTime<T>>, Error> {
+        let mut candidate = date.clone();
+        for _ in 0..MAX_CANDIDATES {
+            match self.a.next(&candidate) {
+                Some(next) if contains(&self.b, &next) => candidate = next,
+                next => return Ok(next),
+            }
+        }
+        Err(Error::SearchLimit)
+    }
+
+    /// Last occurrence strictly before `date`, see [`try_next`](Self::try_next).
+    pub fn try_prev<T: TimeZone>(&self, date: &DateTime<T>) -> Result<Option<DateTime<T>>, Error> {
+        let mut candidate = date.clone();
+        for _ in 0..MAX_CANDIDATES {
+            match self.a.prev(&candidate) {
+                Some(prev) if contains(&self.b, &prev) => candidate = prev,
+                prev => return Ok(prev),
+            }
+        }
+        Err(Error::SearchLimit)
+    }
+}
+
+impl<A: Recurrent, B: Recurrent> Recurrent for Difference<A, B> {
+    /// See [`try_next`](Difference::try_next), giving up being an end.
+    fn next<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>> {
+        self.try_next(date).ok().flatten()
+    }
+
+    fn prev<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>> {
+        self.try_prev(date).ok().flatten()
+    }
+
+    /// The first side's, which the second one is expected to share.
+    fn dst_policy(&self) -> (Gap, Fold) {
+        self.a.dst_policy()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, MissingDay, Recurrence, Recurrent};
+    use chrono::{DateTime, NaiveTime, Utc, Weekday};
+
+    fn parse(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn nine() -> NaiveTime {
+        NaiveTime::from_hms_opt(9, 0, 0).unwrap()
+    }
+
+    fn weekdays() -> Recurrence<crate::OrderedWeekday> {
+        let days = (
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        );
+        Recurrence::new(days, nine()).unwrap()
+    }
+
+    #[test]
+    fn difference() {
+        // weekdays at 09:00 except the first monday of the month
+        let first_monday = Recurrence::monthly_nth(&[(1, Weekday::Mon)], nine()).unwrap();
+        let on_call = weekdays().difference(first_monday);
+        assert_eq!(
+            on_call
+                .occurrences_after(&parse("2020-09-04T09:00:00Z"))
+                .take(2)
+                .collect::<Vec<_>>(),
+            vec![parse("2020-09-08T09:00:00Z"), parse("2020-09-09T09:00:00Z")]
+        );
+        assert_eq!(
+            on_call.prev(&parse("2020-09-08T09:00:00Z")),
+            Some(parse("2020-09-04T09:00:00Z"))
+        );
+        // always matched
+        let none = weekdays().difference(weekdays());
+        assert_eq!(none.prev(&parse("2020-09-08T09:00:00Z")), None);
+        assert!(matches!(
+            none.try_prev(&parse("2020-09-08T09:00:00Z")),
+            Err(Error::SearchLimit)
+        ));
+    }
+
+    #[test]
+    fn union() {
+        let a = Recurrence::new(Weekday::Mon, nine()).unwrap();
+        let b = Recurrence::monthly(&[1], nine(), MissingDay::Skip).unwrap();
+        let either = a.union(&b);
+        assert_eq!(
+            either
+                .between(
+                    &parse("2020-08-30T00:00:00Z"),
+                    &parse("2020-09-14T09:00:00Z"),
+                    true
+                )
+                .rev()
+                .collect::<Vec<_>>(),
+            vec![
+                parse("2020-09-14T09:00:00Z"),
+                parse("2020-09-07T09:00:00Z"),
+                parse("2020-09-01T09:00:00Z"),
+                parse("2020-08-31T09:00:00Z"),
+            ]
+        );
+        // the same occurrence on both sides occurs once
+        assert_eq!(
+            Recurrence::new(Weekday::Tue, nine())
+                .unwrap()
+                .union(&b)
+                .occurrences_after(&parse("2020-08-31T00:00:00Z"))
+                .take(2)
+                .collect::<Vec<_>>(),
+            vec![parse("2020-09-01T09:00:00Z"), parse("2020-09-08T09:00:00Z")]
+        );
+    }
+
+    #[test]
+    fn intersection() {
+        let friday = Recurrence::new(Weekday::Fri, nine()).unwrap();
+        let thirteenth = Recurrence::monthly(&[13], nine(), MissingDay::Skip).unwrap();
+        let unlucky = (&friday).intersection(&thirteenth);
+        assert_eq!(
+            unlucky.next(&parse("2020-01-01T00:00:00Z")),
+            Some(parse("2020-03-13T09:00:00Z"))
+        );
+        assert_eq!(
+            unlucky.next(&parse("2020-03-13T09:00:00Z")),
+            Some(parse("2020-11-13T09:00:00Z"))
+        );
+        assert_eq!(
+            unlucky.prev(&parse("2020-03-13T09:00:00Z")),
+            Some(parse("2019-12-13T09:00:00Z"))
+        );
+        // never together
+        let monday = Recurrence::new(Weekday::Mon, nine()).unwrap();
+        let never = (&friday).intersection(&monday);
+        assert_eq!(never.next(&parse("2020-01-01T00:00:00Z")), None);
+        assert!(matches!(
+            never.try_next(&parse("2020-01-01T00:00:00Z")),
+            Err(Error::SearchLimit)
+        ));
+        // ended
+        let until = monday.with_until(parse("2020-01-07T00:00:00Z").naive_utc());
+        assert!(matches!(
+            (&thirteenth)
+                .intersection(until)
+                .try_next(&parse("2020-01-01T00:00:00Z")),
+            Ok(None)
+        ));
+        assert!(matches!(
+            friday
+                .intersection(thirteenth)
+                .try_prev(&parse("2020-01-01T00:00:00Z")),
+            Ok(Some(_))
+        ));
+    }
+}
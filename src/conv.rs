@@ -1,5 +1,6 @@
-use crate::{OrderedWeekday, Weekdays};
-use chrono::Weekday;
+use crate::{OrderedWeekday, Times, Weekdays};
+use chrono::{NaiveTime, Weekday};
+use std::collections::BTreeSet;
 
 impl Weekdays for Weekday {
     fn week_days(&self) -> Vec<Weekday> {
@@ -63,6 +64,47 @@ impl From<Weekday> for OrderedWeekday {
 
 impl Weekdays for &[Weekday] {
     fn week_days(&self) -> Vec<Weekday> {
-        self.iter().map(|w| w.clone()).collect()
+        self.to_vec()
+    }
+}
+
+impl Times for NaiveTime {
+    fn times(&self) -> Vec<NaiveTime> {
+        vec![*self]
+    }
+}
+impl Times for (NaiveTime, NaiveTime) {
+    fn times(&self) -> Vec<NaiveTime> {
+        vec![self.0, self.1]
+    }
+}
+impl Times for (NaiveTime, NaiveTime, NaiveTime) {
+    fn times(&self) -> Vec<NaiveTime> {
+        vec![self.0, self.1, self.2]
+    }
+}
+impl Times for &[NaiveTime] {
+    fn times(&self) -> Vec<NaiveTime> {
+        self.to_vec()
+    }
+}
+impl Times for BTreeSet<NaiveTime> {
+    fn times(&self) -> Vec<NaiveTime> {
+        self.iter().copied().collect()
+    }
+}
+
+impl From<OrderedWeekday> for Weekday {
+    fn from(w: OrderedWeekday) -> Self {
+        use OrderedWeekday::*;
+        match w {
+            Mon => Weekday::Mon,
+            Tue => Weekday::Tue,
+            Wed => Weekday::Wed,
+            Thu => Weekday::Thu,
+            Fri => Weekday::Fri,
+            Sat => Weekday::Sat,
+            Sun => Weekday::Sun,
+        }
     }
 }
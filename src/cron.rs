@@ -0,0 +1,404 @@
+use crate::yearly::YearlyDay;
+use crate::{
+    DayOfMonth, DayOfYear, Error, Fold, Gap, MissingDay, OrderedWeekday, Recurrence, Recurrent,
+};
+use chrono::{DateTime, NaiveTime, TimeZone, Timelike, Weekday};
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+/// A recurrence parsed from a cron expression.
+///
+/// Both 5 fields (`minute hour day-of-month month day-of-week`) and 6 fields (seconds first)
+/// expressions are supported, with lists, ranges, steps, `JAN`-`DEC` and `SUN`-`SAT` names
+/// (`0` and `7` are sunday) and the `@yearly`, `@monthly`, `@weekly`, `@daily` and `@hourly`
+/// shortcuts. Restricting both the day of month and the day of week is not supported.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Cron {
+    /// Some days of week, or every day.
+    Weekly(Recurrence<OrderedWeekday>),
+    /// Some days of every month.
+    Monthly(Recurrence<DayOfMonth>),
+    /// Some days of some months.
+    Yearly(Recurrence<DayOfYear>),
+}
+
+impl Recurrent for Cron {
+    fn next<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>> {
+        match self {
+            Cron::Weekly(recurrence) => recurrence.next(date),
+            Cron::Monthly(recurrence) => recurrence.next(date),
+            Cron::Yearly(recurrence) => recurrence.next(date),
+        }
+    }
+
+    fn prev<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>> {
+        match self {
+            Cron::Weekly(recurrence) => recurrence.prev(date),
+            Cron::Monthly(recurrence) => recurrence.prev(date),
+            Cron::Yearly(recurrence) => recurrence.prev(date),
+        }
+    }
+
+    fn dst_policy(&self) -> (Gap, Fold) {
+        match self {
+            Cron::Weekly(recurrence) => recurrence.dst_policy(),
+            Cron::Monthly(recurrence) => recurrence.dst_policy(),
+            Cron::Yearly(recurrence) => recurrence.dst_policy(),
+        }
+    }
+}
+
+const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const WEEKDAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+const ALL_WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+fn invalid(field: &str) -> Error {
+    Error::InvalidCron(field.to_string())
+}
+
+/// Values of a field, `None` when unrestricted.
+fn field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<Option<Vec<u32>>, Error> {
+    if field == "*" || field == "?" {
+        return Ok(None);
+    }
+    let value = |s: &str| -> Result<u32, Error> {
+        let upper = s.to_ascii_uppercase();
+        let value = match names.iter().position(|name| *name == upper) {
+            Some(index) => index as u32 + min,
+            None => s.parse().map_err(|_| invalid(field))?,
+        };
+        if value < min || value > max {
+            return Err(invalid(field));
+        }
+        Ok(value)
+    };
+    let mut values = BTreeSet::new();
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (
+                range,
+                Some(step.parse::<u32>().map_err(|_| invalid(field))?),
+            ),
+            None => (item, None),
+        };
+        if step == Some(0) {
+            return Err(invalid(field));
+        }
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (value(start)?, value(end)?),
+            // a single value with a step runs up to the end
+            None if step.is_some() => (value(range)?, max),
+            None => (value(range)?, value(range)?),
+        };
+        if end < start {
+            return Err(invalid(field));
+        }
+        values.extend((start..=end).step_by(step.unwrap_or(1) as usize));
+    }
+    Ok(Some(values.into_iter().collect()))
+}
+
+/// A field of `values`, `*` when they are all of `min..=max`: `1-5`, `0,30`...
+fn format_field(values: &BTreeSet<u32>, min: u32, max: u32) -> String {
+    if values.len() as u32 == max - min + 1 {
+        return "*".to_string();
+    }
+    let mut items = vec![];
+    let mut values = values.iter().copied().peekable();
+    while let Some(start) = values.next() {
+        let mut end = start;
+        while values.peek() == Some(&(end + 1)) {
+            end += 1;
+            values.next();
+        }
+        items.push(match end - start {
+            0 => start.to_string(),
+            1 => format!("{},{}", start, end),
+            _ => format!("{}-{}", start, end),
+        });
+    }
+    items.join(",")
+}
+
+/// The day of week field of `days`, sunday being 0.
+fn format_weekdays(days: impl Iterator<Item = OrderedWeekday>) -> String {
+    let days = days.map(|day| (day as u32 + 1) % 7).collect();
+    format_field(&days, 0, 6)
+}
+
+impl Cron {
+    /// The expression parsing back to this cron, 6 fields long when some times have seconds.
+    /// Fails for recurrences no expression describes, e.g. ones with an interval or on the last
+    /// day of the month.
+    pub fn to_cron(&self) -> Result<String, Error> {
+        let (times, days, months, weekdays) = match self {
+            Cron::Weekly(recurrence) => (
+                &recurrence.times,
+                "*".to_string(),
+                "*".to_string(),
+                format_weekdays(recurrence.days.iter().copied()),
+            ),
+            Cron::Monthly(recurrence) => {
+                let days = recurrence.days.iter().map(|day| day.day().max(0) as u32);
+                (
+                    &recurrence.times,
+                    format_field(&days.collect(), 1, 31),
+                    "*".to_string(),
+                    "*".to_string(),
+                )
+            }
+            Cron::Yearly(recurrence) => {
+                let months = recurrence.days.iter().map(|day| day.month()).collect();
+                let (mut days, mut weekdays) = (BTreeSet::new(), vec![]);
+                for day in &recurrence.days {
+                    match day.day() {
+                        YearlyDay::Date(date) => {
+                            days.insert(date.day().max(0) as u32);
+                        }
+                        YearlyDay::Nth(nth) => weekdays.push(nth.weekday()),
+                    }
+                }
+                let (days, weekdays) = if weekdays.is_empty() {
+                    (format_field(&days, 1, 31), "*".to_string())
+                } else {
+                    ("*".to_string(), format_weekdays(weekdays.into_iter()))
+                };
+                (
+                    &recurrence.times,
+                    days,
+                    format_field(&months, 1, 12),
+                    weekdays,
+                )
+            }
+        };
+        let times = times.to_set();
+        let field = |part: fn(&NaiveTime) -> u32| times.iter().map(part).collect();
+        let seconds: BTreeSet<u32> = field(NaiveTime::second);
+        let mut fields = vec![
+            format_field(&field(NaiveTime::minute), 0, 59),
+            format_field(&field(NaiveTime::hour), 0, 23),
+            days,
+            months,
+            weekdays,
+        ];
+        if seconds.iter().any(|second| *second != 0) {
+            fields.insert(0, format_field(&seconds, 0, 59));
+        }
+        let expression = fields.join(" ");
+        match expression.parse::<Cron>() {
+            Ok(parsed) if parsed == *self => Ok(expression),
+            _ => Err(Error::UnsupportedCron(
+                "a recurrence no expression describes".to_string(),
+            )),
+        }
+    }
+}
+
+impl FromStr for Cron {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let expanded = match s.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            s => s,
+        };
+        let fields: Vec<_> = expanded.split_whitespace().collect();
+        let (seconds, fields) = match fields.len() {
+            5 => (None, &fields[..]),
+            6 => (Some(fields[0]), &fields[1..]),
+            _ => return Err(invalid(s)),
+        };
+        let seconds = match seconds {
+            Some(seconds) => field(seconds, 0, 59, &[])?.unwrap_or_else(|| (0..60).collect()),
+            None => vec![0],
+        };
+        let minutes = field(fields[0], 0, 59, &[])?.unwrap_or_else(|| (0..60).collect());
+        let hours = field(fields[1], 0, 23, &[])?.unwrap_or_else(|| (0..24).collect());
+        let days = field(fields[2], 1, 31, &[])?;
+        let months = field(fields[3], 1, 12, &MONTHS)?;
+        let weekdays = field(fields[4], 0, 7, &WEEKDAYS)?;
+
+        let mut times = BTreeSet::new();
+        for hour in &hours {
+            for minute in &minutes {
+                for second in &seconds {
+                    times.extend(NaiveTime::from_hms_opt(*hour, *minute, *second));
+                }
+            }
+        }
+        let weekdays: Option<BTreeSet<OrderedWeekday>> = weekdays.map(|weekdays| {
+            weekdays
+                .into_iter()
+                // cron weeks start on sunday
+                .map(|day| ALL_WEEKDAYS[(day as usize + 6) % 7].into())
+                .collect()
+        });
+        match (days, months, weekdays) {
+            (Some(_), _, Some(_)) => Err(Error::UnsupportedCron(
+                "both day of month and day of week".to_string(),
+            )),
+            (None, None, weekdays) => {
+                let weekdays =
+                    weekdays.unwrap_or_else(|| ALL_WEEKDAYS.iter().map(|d| (*d).into()).collect());
+                Ok(Cron::Weekly(Recurrence::from_days(weekdays, times)?))
+            }
+            (Some(days), None, None) => {
+                let days = days
+                    .into_iter()
+                    .map(|day| DayOfMonth::new(day as i8, MissingDay::Skip))
+                    .collect::<Result<_, _>>()?;
+                Ok(Cron::Monthly(Recurrence::from_days(days, times)?))
+            }
+            (days, Some(months), None) => {
+                let days = days.unwrap_or_else(|| (1..=31).collect());
+                // dates that never exist (e.g. april 31st) never fire
+                let days: BTreeSet<_> = months
+                    .iter()
+                    .flat_map(|month| {
+                        days.iter().filter_map(move |day| {
+                            DayOfYear::date(*month, *day, MissingDay::Skip).ok()
+                        })
+                    })
+                    .collect();
+                Ok(Cron::Yearly(Recurrence::from_days(days, times)?))
+            }
+            (None, Some(months), Some(weekdays)) => {
+                let mut days = BTreeSet::new();
+                for month in months {
+                    for weekday in &weekdays {
+                        for nth in 1..=5 {
+                            days.insert(DayOfYear::nth_weekday(month, nth, (*weekday).into())?);
+                        }
+                    }
+                }
+                Ok(Cron::Yearly(Recurrence::from_days(days, times)?))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cron, Error, MissingDay, Recurrent};
+    use chrono::{DateTime, Utc};
+
+    fn parse(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn next(cron: &str, now: &str) -> DateTime<Utc> {
+        cron.parse::<Cron>().unwrap().next(&parse(now)).unwrap()
+    }
+
+    #[test]
+    fn cron() {
+        // friday evening
+        assert_eq!(
+            next("0 9 * * MON-FRI", "2020-09-04T17:00:00Z"),
+            parse("2020-09-07T09:00:00Z")
+        );
+        assert_eq!(
+            next("*/15 8-17 * * 1-5", "2020-09-04T17:50:00Z"),
+            parse("2020-09-07T08:00:00Z")
+        );
+        assert_eq!(
+            next("*/15 8-17 * * 1-5", "2020-09-04T12:05:00Z"),
+            parse("2020-09-04T12:15:00Z")
+        );
+        assert_eq!(
+            next("30 0 12 * * sun,7", "2020-09-04T17:00:00Z"),
+            parse("2020-09-06T12:00:30Z")
+        );
+        assert_eq!(
+            next("0 0 1,15 * *", "2020-09-04T17:00:00Z"),
+            parse("2020-09-15T00:00:00Z")
+        );
+        assert_eq!(
+            next("0 0 31 * *", "2020-09-04T17:00:00Z"),
+            parse("2020-10-31T00:00:00Z")
+        );
+        assert_eq!(
+            next("0 12 29 FEB *", "2021-01-01T00:00:00Z"),
+            parse("2024-02-29T12:00:00Z")
+        );
+        assert_eq!(
+            next("0 12 * dec fri", "2020-09-04T17:00:00Z"),
+            parse("2020-12-04T12:00:00Z")
+        );
+        assert_eq!(
+            next("0 12 * 2/6 *", "2020-09-04T17:00:00Z"),
+            parse("2021-02-01T12:00:00Z")
+        );
+        assert_eq!(
+            next("@weekly", "2020-09-04T17:00:00Z"),
+            parse("2020-09-06T00:00:00Z")
+        );
+        let cron: Cron = "0 9 * * MON-FRI".parse().unwrap();
+        assert_eq!(
+            cron.prev(&parse("2020-09-07T09:00:00Z")).unwrap(),
+            parse("2020-09-04T09:00:00Z")
+        );
+    }
+
+    #[test]
+    fn format() {
+        for (cron, expected) in &[
+            ("0 9 * * MON-FRI", "0 9 * * 1-5"),
+            ("*/15 8-17 * * 1-5", "0,15,30,45 8-17 * * 1-5"),
+            ("30 0 12 * * sun,7", "30 0 12 * * 0"),
+            ("0 0 1,15 * *", "0 0 1,15 * *"),
+            ("0 12 29 FEB *", "0 12 29 2 *"),
+            ("0 12 * dec fri", "0 12 * 12 5"),
+            ("@monthly", "0 0 1 * *"),
+            ("* * * * *", "* * * * *"),
+        ] {
+            let cron: Cron = cron.parse().unwrap();
+            assert_eq!(cron.to_cron().unwrap(), *expected);
+            assert_eq!(expected.parse::<Cron>().unwrap(), cron);
+        }
+        let last_day = crate::Recurrence::monthly(&[-1], chrono::NaiveTime::MIN, MissingDay::Skip);
+        assert!(matches!(
+            Cron::Monthly(last_day.unwrap()).to_cron(),
+            Err(Error::UnsupportedCron(_))
+        ));
+    }
+
+    #[test]
+    fn invalid() {
+        for cron in &[
+            "* * * *",
+            "60 * * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "* * * * 8",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "* * * * FOO",
+        ] {
+            assert!(
+                matches!(cron.parse::<Cron>(), Err(Error::InvalidCron(_))),
+                "{}",
+                cron
+            );
+        }
+        assert!(matches!(
+            "0 0 13 * FRI".parse::<Cron>(),
+            Err(Error::UnsupportedCron(_))
+        ));
+    }
+}
@@ -0,0 +1,1004 @@
+use crate::times::DayTimes;
+use crate::yearly::YearlyDay;
+use crate::{
+    BusinessDays, Cron, DayOfMonth, DayOfYear, HolidayCalendar, Locale, MissingDay, NthWeekday,
+    OrderedWeekday, Recurrence, RecurrentDay, Schedule, WeekStart,
+};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use std::collections::BTreeSet;
+
+/// `a`, `a and b`, `a, b and c`...
+fn list<T: AsRef<str>>(items: &[T], locale: Locale) -> String {
+    let and = match locale {
+        Locale::En => "and",
+        Locale::Fr => "et",
+        Locale::De => "und",
+        Locale::Es => "y",
+    };
+    match items {
+        [] => String::new(),
+        [item] => item.as_ref().to_string(),
+        [items @ .., last] => {
+            let items: Vec<_> = items.iter().map(AsRef::as_ref).collect();
+            format!("{} {} {}", items.join(", "), and, last.as_ref())
+        }
+    }
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// `1st`, `2nd`, `11th`, `23rd`... in English, `1er`, `2e` in French.
+fn ordinal(n: i64, locale: Locale) -> String {
+    match locale {
+        Locale::En => {
+            let suffix = match (n % 10, n % 100) {
+                (_, 11..=13) => "th",
+                (1, _) => "st",
+                (2, _) => "nd",
+                (3, _) => "rd",
+                _ => "th",
+            };
+            format!("{}{}", n, suffix)
+        }
+        Locale::Fr if n == 1 => "1er".to_string(),
+        Locale::Fr => format!("{}e", n),
+        Locale::De => format!("{}.", n),
+        Locale::Es if n == 1 || n == 3 => format!("{}.er", n),
+        Locale::Es => format!("{}.º", n),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Unit {
+    Week,
+    Month,
+    Year,
+}
+
+/// `every week`, `every other week`, `every 3 weeks`.
+fn every(interval: u32, unit: Unit, locale: Locale) -> String {
+    let (one, other, many) = match (locale, unit) {
+        (Locale::En, Unit::Week) => ("every week", "every other week", "every {} weeks"),
+        (Locale::En, Unit::Month) => ("every month", "every other month", "every {} months"),
+        (Locale::En, Unit::Year) => ("every year", "every other year", "every {} years"),
+        (Locale::Fr, Unit::Week) => (
+            "chaque semaine",
+            "une semaine sur deux",
+            "toutes les {} semaines",
+        ),
+        (Locale::Fr, Unit::Month) => ("chaque mois", "un mois sur deux", "tous les {} mois"),
+        (Locale::Fr, Unit::Year) => ("chaque année", "une année sur deux", "tous les {} ans"),
+        (Locale::De, Unit::Week) => ("jede Woche", "jede zweite Woche", "alle {} Wochen"),
+        (Locale::De, Unit::Month) => ("jeden Monat", "jeden zweiten Monat", "alle {} Monate"),
+        (Locale::De, Unit::Year) => ("jedes Jahr", "jedes zweite Jahr", "alle {} Jahre"),
+        (Locale::Es, Unit::Week) => ("cada semana", "cada dos semanas", "cada {} semanas"),
+        (Locale::Es, Unit::Month) => ("cada mes", "cada dos meses", "cada {} meses"),
+        (Locale::Es, Unit::Year) => ("cada año", "cada dos años", "cada {} años"),
+    };
+    match interval {
+        1 => one.to_string(),
+        2 => other.to_string(),
+        n => many.replace("{}", &n.to_string()),
+    }
+}
+
+/// Days counted from the start first, then the ones counted from the end.
+fn from_start_first(nth: i64) -> (bool, i64) {
+    (nth < 0, nth)
+}
+
+/// `2nd`, `last`, `2nd to last`.
+fn nth(n: i64) -> String {
+    match n {
+        -1 => "last".to_string(),
+        n if n < 0 => format!("{} to last", ordinal(-n, Locale::En)),
+        n => ordinal(n, Locale::En),
+    }
+}
+
+fn time(time: NaiveTime, locale: Locale) -> String {
+    let format = match (locale, time.second()) {
+        (Locale::En, 0) => "%-I:%M %p",
+        (Locale::En, _) => "%-I:%M:%S %p",
+        (_, 0) => "%H:%M",
+        (_, _) => "%H:%M:%S",
+    };
+    time.format(format).to_string()
+}
+
+fn date(date: NaiveDate, locale: Locale) -> String {
+    let month = locale.month_name(date.month()).unwrap();
+    let (day, year) = (date.day(), date.year());
+    match locale {
+        Locale::En => format!("{} {}, {}", month, day, year),
+        Locale::Fr if day == 1 => format!("1er {} {}", month, year),
+        Locale::Fr => format!("{} {} {}", day, month, year),
+        Locale::De => format!("{}. {} {}", day, month, year),
+        Locale::Es => format!("{} de {} de {}", day, month, year),
+    }
+}
+
+fn date_time(date_time: NaiveDateTime, locale: Locale) -> String {
+    let at = match locale {
+        Locale::En => "at",
+        Locale::Fr => "à",
+        Locale::De => "um",
+        Locale::Es => "a las",
+    };
+    format!(
+        "{} {} {}",
+        date(date_time.date(), locale),
+        at,
+        time(date_time.time(), locale)
+    )
+}
+
+fn duration(seconds: i64, locale: Locale) -> String {
+    let (count, unit) = if seconds % 3600 == 0 {
+        (seconds / 3600, 0)
+    } else if seconds % 60 == 0 {
+        (seconds / 60, 1)
+    } else {
+        (seconds, 2)
+    };
+    let (one, many) = match locale {
+        Locale::En => (
+            ["every hour", "every minute", "every second"],
+            ["every {} hours", "every {} minutes", "every {} seconds"],
+        ),
+        Locale::Fr => (
+            [
+                "toutes les heures",
+                "toutes les minutes",
+                "toutes les secondes",
+            ],
+            [
+                "toutes les {} heures",
+                "toutes les {} minutes",
+                "toutes les {} secondes",
+            ],
+        ),
+        Locale::De => (
+            ["jede Stunde", "jede Minute", "jede Sekunde"],
+            ["alle {} Stunden", "alle {} Minuten", "alle {} Sekunden"],
+        ),
+        Locale::Es => (
+            ["cada hora", "cada minuto", "cada segundo"],
+            ["cada {} horas", "cada {} minutos", "cada {} segundos"],
+        ),
+    };
+    match count {
+        1 => one[unit].to_string(),
+        count => many[unit].replace("{}", &count.to_string()),
+    }
+}
+
+/// ` at 9:00 AM`, or `, every 15 minutes from 8:00 AM to 6:00 PM`.
+fn times(times: &DayTimes, locale: Locale) -> String {
+    let set = times.to_set();
+    let set: Vec<_> = set.into_iter().collect();
+    // e.g. parsed from a cron expression
+    let steps: BTreeSet<_> = set
+        .windows(2)
+        .map(|w| (w[1] - w[0]).num_seconds())
+        .collect();
+    let range = |step: i64, from: NaiveTime, to: NaiveTime| {
+        let (from_word, to_word) = match locale {
+            Locale::En => ("from", "to"),
+            Locale::Fr => ("de", "à"),
+            Locale::De => ("von", "bis"),
+            Locale::Es => ("de", "a"),
+        };
+        format!(
+            ", {} {} {} {} {}",
+            duration(step, locale),
+            from_word,
+            time(from, locale),
+            to_word,
+            time(to, locale)
+        )
+    };
+    match (times, steps.len()) {
+        (DayTimes::Every { from, to, step }, _) => range(*step, *from, *to),
+        (_, 1) if set.len() > 2 => range(*steps.iter().next().unwrap(), set[0], set[set.len() - 1]),
+        _ => {
+            let at = match locale {
+                Locale::En => "at",
+                Locale::Fr => "à",
+                Locale::De => "um",
+                Locale::Es => "a las",
+            };
+            let times: Vec<_> = set.into_iter().map(|t| time(t, locale)).collect();
+            format!(" {} {}", at, list(&times, locale))
+        }
+    }
+}
+
+/// Days of a recurrence, in words.
+trait DescribeDay: RecurrentDay {
+    /// e.g. `Every Monday and Friday`, repeating every `interval` weeks (months, years...),
+    /// weekdays listed from `week_start`.
+    fn describe(
+        days: &BTreeSet<Self>,
+        interval: u32,
+        week_start: WeekStart,
+        locale: Locale,
+    ) -> String;
+}
+
+/// `lundis`, `sábados`... for `tous les` and `cada dos`.
+fn plural(name: &str) -> String {
+    if name.ends_with('s') {
+        name.to_string()
+    } else {
+        format!("{}s", name)
+    }
+}
+
+impl DescribeDay for OrderedWeekday {
+    fn describe(
+        days: &BTreeSet<Self>,
+        interval: u32,
+        week_start: WeekStart,
+        locale: Locale,
+    ) -> String {
+        let names: Vec<_> = week_start
+            .days()
+            .filter(|day| days.contains(day))
+            .map(|day| day.name(locale))
+            .collect();
+        let all = days.len() == 7;
+        let weekdays = days.len() == 5
+            && !days.contains(&OrderedWeekday::Sat)
+            && !days.contains(&OrderedWeekday::Sun);
+        if locale == Locale::En {
+            let noun = match (all, weekdays) {
+                (true, _) => "day".to_string(),
+                (_, true) => "weekday".to_string(),
+                _ => list(&names, locale),
+            };
+            let every = capitalize(&every(interval, Unit::Week, locale));
+            return match (interval, names.as_slice(), all, weekdays) {
+                (1, _, _, _) => format!("Every {}", noun),
+                (2, [name], _, _) => format!("Every other {}", name),
+                (_, _, true, _) => format!("{} on every day", every),
+                (_, _, _, true) => format!("{} on weekdays", every),
+                _ => format!("{} on {}", every, noun),
+            };
+        }
+        if let (2, [name]) = (interval, names.as_slice()) {
+            return match locale {
+                Locale::Fr => format!("un {} sur deux", name),
+                Locale::De => format!("jeden zweiten {}", name),
+                _ => format!("cada dos {}", plural(name)),
+            };
+        }
+        let days = match (locale, all, weekdays) {
+            (Locale::Fr, true, _) => "tous les jours".to_string(),
+            (Locale::Fr, _, true) => "du lundi au vendredi".to_string(),
+            (Locale::Fr, _, _) => {
+                let names: Vec<_> = names.iter().map(|name| plural(name)).collect();
+                format!("tous les {}", list(&names, locale))
+            }
+            (Locale::De, true, _) => "jeden Tag".to_string(),
+            (Locale::De, _, true) => "von Montag bis Freitag".to_string(),
+            (Locale::De, _, _) => format!("jeden {}", list(&names, locale)),
+            (_, true, _) => "todos los días".to_string(),
+            (_, _, true) => "de lunes a viernes".to_string(),
+            (_, _, _) => format!("cada {}", list(&names, locale)),
+        };
+        match interval {
+            1 => days,
+            interval => format!("{}, {}", days, every(interval, Unit::Week, locale)),
+        }
+    }
+}
+
+/// e.g. ` of every month` or `, every 3 months`.
+fn of_every_month(interval: u32, locale: Locale) -> String {
+    let every = every(interval, Unit::Month, locale);
+    match (locale, interval) {
+        (Locale::En, _) => format!(" of {}", every),
+        (Locale::Fr, 1) => " de chaque mois".to_string(),
+        (Locale::De, 1) => " jedes Monats".to_string(),
+        (Locale::Es, 1) => " de cada mes".to_string(),
+        _ => format!(", {}", every),
+    }
+}
+
+fn day_of_month(day: DayOfMonth, locale: Locale) -> String {
+    let day = day.day() as i64;
+    match (locale, day) {
+        (Locale::En, day) if day < 0 => format!("{} day", nth(day)),
+        (Locale::En, day) => ordinal(day, locale),
+        (Locale::Fr, 1) => "le 1er".to_string(),
+        (Locale::Fr, -1) => "le dernier jour".to_string(),
+        (Locale::Fr, -2) => "l'avant-dernier jour".to_string(),
+        (Locale::Fr, day) if day < 0 => {
+            format!("le {} jour en partant de la fin", ordinal(-day, locale))
+        }
+        (Locale::Fr, day) => format!("le {}", day),
+        (Locale::De, -1) => "letzten Tag".to_string(),
+        (Locale::De, -2) => "vorletzten Tag".to_string(),
+        (Locale::De, day) if day < 0 => format!("{}letzten Tag", german_stem(-day)),
+        (Locale::De, day) => ordinal(day, locale),
+        (Locale::Es, -1) => "el último día".to_string(),
+        (Locale::Es, -2) => "el penúltimo día".to_string(),
+        (Locale::Es, day) if day < 0 => {
+            format!("el {} día contando desde el final", ordinal(-day, locale))
+        }
+        (Locale::Es, day) => format!("el {}", day),
+    }
+}
+
+/// ` (or the last day of shorter months)`... for a day some months lack, of every month or of
+/// February every year.
+fn missing_day(day: DayOfMonth, yearly: bool, locale: Locale) -> String {
+    if day.day() <= 28 {
+        return String::new();
+    }
+    let description = match (day.missing(), yearly, locale) {
+        (MissingDay::Skip, _, _) => return String::new(),
+        (MissingDay::Clamp, false, Locale::En) => "or the last day of shorter months",
+        (MissingDay::Clamp, false, Locale::Fr) => "ou le dernier jour des mois plus courts",
+        (MissingDay::Clamp, false, Locale::De) => "oder am letzten Tag kürzerer Monate",
+        (MissingDay::Clamp, false, Locale::Es) => "o el último día de los meses más cortos",
+        (MissingDay::Rollover, false, Locale::En) => "or the 1st of the next month",
+        (MissingDay::Rollover, false, Locale::Fr) => "ou le 1er du mois suivant",
+        (MissingDay::Rollover, false, Locale::De) => "oder am 1. des Folgemonats",
+        (MissingDay::Rollover, false, Locale::Es) => "o el 1 del mes siguiente",
+        (MissingDay::Clamp, true, Locale::En) => "or February 28 in other years",
+        (MissingDay::Clamp, true, Locale::Fr) => "ou le 28 février les autres années",
+        (MissingDay::Clamp, true, Locale::De) => "oder am 28. Februar in anderen Jahren",
+        (MissingDay::Clamp, true, Locale::Es) => "o el 28 de febrero los demás años",
+        (MissingDay::Rollover, true, Locale::En) => "or March 1 in other years",
+        (MissingDay::Rollover, true, Locale::Fr) => "ou le 1er mars les autres années",
+        (MissingDay::Rollover, true, Locale::De) => "oder am 1. März in anderen Jahren",
+        (MissingDay::Rollover, true, Locale::Es) => "o el 1 de marzo los demás años",
+    };
+    format!(" ({})", description)
+}
+
+/// `dritt`, `zwanzigst`... as in `drittletzten`, for `n` up to 39.
+fn german_stem(n: i64) -> String {
+    const STEMS: [&str; 19] = [
+        "erst",
+        "zweit",
+        "dritt",
+        "viert",
+        "fünft",
+        "sechst",
+        "siebt",
+        "acht",
+        "neunt",
+        "zehnt",
+        "elft",
+        "zwölft",
+        "dreizehnt",
+        "vierzehnt",
+        "fünfzehnt",
+        "sechzehnt",
+        "siebzehnt",
+        "achtzehnt",
+        "neunzehnt",
+    ];
+    const UNITS: [&str; 10] = [
+        "", "ein", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+    ];
+    match n {
+        1..=19 => STEMS[n as usize - 1].to_string(),
+        n => {
+            let tens = if n < 30 { "zwanzig" } else { "dreißig" };
+            match UNITS[n as usize % 10] {
+                "" => format!("{}st", tens),
+                unit => format!("{}und{}st", unit, tens),
+            }
+        }
+    }
+}
+
+const NTHS: [[&str; 5]; 4] = [
+    ["first", "second", "third", "fourth", "fifth"],
+    ["premier", "deuxième", "troisième", "quatrième", "cinquième"],
+    ["ersten", "zweiten", "dritten", "vierten", "fünften"],
+    ["primer", "segundo", "tercer", "cuarto", "quinto"],
+];
+
+/// `first Monday`, `le dernier lundi`, `vorletzten Montag`...
+fn nth_weekday(day: NthWeekday, locale: Locale) -> String {
+    let nths = &NTHS[locale as usize];
+    let weekday = day.weekday().name(locale);
+    match (locale, day.nth()) {
+        (Locale::En, -1) => format!("last {}", weekday),
+        (Locale::En, n) if n < 0 => format!("{} to last {}", nths[(-n - 1) as usize], weekday),
+        (Locale::Fr, -1) => format!("le dernier {}", weekday),
+        (Locale::Fr, -2) => format!("l'avant-dernier {}", weekday),
+        (Locale::Fr, n) if n < 0 => format!(
+            "le {} {} en partant de la fin",
+            nths[(-n - 1) as usize],
+            weekday
+        ),
+        (Locale::Fr, n) => format!("le {} {}", nths[(n - 1) as usize], weekday),
+        (Locale::De, -1) => format!("letzten {}", weekday),
+        (Locale::De, -2) => format!("vorletzten {}", weekday),
+        (Locale::De, n) if n < 0 => format!("{}letzten {}", german_stem(-n as i64), weekday),
+        (Locale::Es, -1) => format!("el último {}", weekday),
+        (Locale::Es, -2) => format!("el penúltimo {}", weekday),
+        (Locale::Es, n) if n < 0 => format!(
+            "el {} {} contando desde el final",
+            nths[(-n - 1) as usize],
+            weekday
+        ),
+        (Locale::Es, n) => format!("el {} {}", nths[(n - 1) as usize], weekday),
+        (_, n) => format!("{} {}", nths[(n - 1) as usize], weekday),
+    }
+}
+
+impl DescribeDay for DayOfMonth {
+    fn describe(days: &BTreeSet<Self>, interval: u32, _: WeekStart, locale: Locale) -> String {
+        let mut days: Vec<_> = days.iter().copied().collect();
+        days.sort_by_key(|day| from_start_first(day.day() as i64));
+        let days: Vec<_> = days
+            .into_iter()
+            .map(|day| day_of_month(day, locale) + &missing_day(day, false, locale))
+            .collect();
+        let prefix = match locale {
+            Locale::En => "On the ",
+            Locale::De => "Am ",
+            _ => "",
+        };
+        let days = list(&days, locale);
+        format!("{}{}{}", prefix, days, of_every_month(interval, locale))
+    }
+}
+
+impl DescribeDay for NthWeekday {
+    fn describe(days: &BTreeSet<Self>, interval: u32, _: WeekStart, locale: Locale) -> String {
+        let mut days: Vec<_> = days.iter().copied().collect();
+        days.sort_by_key(|day| (from_start_first(day.nth() as i64), day.weekday()));
+        let days: Vec<_> = days
+            .into_iter()
+            .map(|day| nth_weekday(day, locale))
+            .collect();
+        let prefix = match locale {
+            Locale::En => "On the ",
+            Locale::De => "Am ",
+            _ => "",
+        };
+        let days = list(&days, locale);
+        format!("{}{}{}", prefix, days, of_every_month(interval, locale))
+    }
+}
+
+impl DescribeDay for DayOfYear {
+    fn describe(days: &BTreeSet<Self>, interval: u32, _: WeekStart, locale: Locale) -> String {
+        let days: Vec<_> = days
+            .iter()
+            .map(|day| {
+                let month = locale.month_name(day.month()).unwrap();
+                let missing = match day.day() {
+                    YearlyDay::Date(date) if day.month() == 2 => missing_day(date, true, locale),
+                    _ => String::new(),
+                };
+                let day = match (locale, day.day()) {
+                    (Locale::En, YearlyDay::Date(date)) => format!("{} {}", month, date.day()),
+                    (Locale::En, YearlyDay::Nth(nth)) => {
+                        format!("the {} of {}", nth_weekday(nth, locale), month)
+                    }
+                    (Locale::Fr, YearlyDay::Date(date)) if date.day() == 1 => {
+                        format!("le 1er {}", month)
+                    }
+                    (Locale::Fr, YearlyDay::Date(date)) => format!("le {} {}", date.day(), month),
+                    (Locale::Fr, YearlyDay::Nth(nth)) => {
+                        let of = if month.starts_with(['a', 'o']) {
+                            "d'"
+                        } else {
+                            "de "
+                        };
+                        format!("{} {}{}", nth_weekday(nth, locale), of, month)
+                    }
+                    (Locale::De, YearlyDay::Date(date)) => format!("{}. {}", date.day(), month),
+                    (Locale::De, YearlyDay::Nth(nth)) => {
+                        format!("{} im {}", nth_weekday(nth, locale), month)
+                    }
+                    (Locale::Es, YearlyDay::Date(date)) => {
+                        format!("el {} de {}", date.day(), month)
+                    }
+                    (Locale::Es, YearlyDay::Nth(nth)) => {
+                        format!("{} de {}", nth_weekday(nth, locale), month)
+                    }
+                };
+                day + &missing
+            })
+            .collect();
+        let days = list(&days, locale);
+        let every = every(interval, Unit::Year, locale);
+        match locale {
+            Locale::En => format!("On {} {}", days, every),
+            Locale::De => format!("Am {}, {}", days, every),
+            _ => format!("{}, {}", days, every),
+        }
+    }
+}
+
+fn describe<D: DescribeDay>(recurrence: &Recurrence<D>, locale: Locale) -> String {
+    let interval = recurrence.interval.map_or(1, |interval| interval.every);
+    let days = D::describe(&recurrence.days, interval, recurrence.week_start, locale);
+    capitalize(&days)
+        + &times(&recurrence.times, locale)
+        + &bounds(recurrence.start, recurrence.until, recurrence.count, locale)
+}
+
+/// `, starting March 1, 2021, 10 times`...
+fn bounds(
+    start: Option<NaiveDateTime>,
+    until: Option<NaiveDateTime>,
+    count: Option<u32>,
+    locale: Locale,
+) -> String {
+    let mut description = String::new();
+    let (starting, until_word) = match locale {
+        Locale::En => ("starting", "until"),
+        Locale::Fr => ("à partir du", "jusqu'au"),
+        Locale::De => ("ab", "bis"),
+        Locale::Es => ("a partir del", "hasta el"),
+    };
+    if let Some(start) = start {
+        description += &format!(", {} {}", starting, date(start.date(), locale));
+    }
+    if let Some(until) = until {
+        description += &format!(", {} {}", until_word, date(until.date(), locale));
+    }
+    if let Some(count) = count {
+        description += &match (locale, count) {
+            (Locale::En, 1) => ", once".to_string(),
+            (Locale::En, count) => format!(", {} times", count),
+            (Locale::Fr, 1) => ", une fois".to_string(),
+            (Locale::Fr, count) => format!(", {} fois", count),
+            (Locale::De, 1) => ", einmal".to_string(),
+            (Locale::De, count) => format!(", {}-mal", count),
+            (Locale::Es, 1) => ", una vez".to_string(),
+            (Locale::Es, count) => format!(", {} veces", count),
+        };
+    }
+    description
+}
+
+fn describe_schedule<D: DescribeDay>(schedule: &Schedule<D>, locale: Locale) -> String {
+    let mut description = describe(schedule.recurrence(), locale);
+    let (except, also, with, moved_to) = match locale {
+        Locale::En => ("except on", "also on", "with", "{} moved to {}"),
+        Locale::Fr => ("sauf le", "ainsi que le", "avec", "le {} déplacé au {}"),
+        Locale::De => ("außer am", "zusätzlich am", "mit", "{} verschoben auf {}"),
+        Locale::Es => ("excepto el", "y también el", "con", "el {} movido al {}"),
+    };
+    let mut excluded: Vec<_> = schedule
+        .excluded_dates()
+        .iter()
+        .map(|excluded| {
+            (
+                excluded.and_hms_opt(0, 0, 0).unwrap(),
+                date(*excluded, locale),
+            )
+        })
+        .chain(
+            schedule
+                .excluded()
+                .iter()
+                .map(|excluded| (*excluded, date_time(*excluded, locale))),
+        )
+        .collect();
+    if !excluded.is_empty() {
+        excluded.sort();
+        let excluded: Vec<_> = excluded.into_iter().map(|(_, excluded)| excluded).collect();
+        description += &format!(", {} {}", except, list(&excluded, locale));
+    }
+    let added: Vec<_> = schedule
+        .added()
+        .iter()
+        .map(|added| date_time(*added, locale))
+        .collect();
+    if !added.is_empty() {
+        description += &format!(", {} {}", also, list(&added, locale));
+    }
+    let moved: Vec<_> = schedule
+        .rescheduled()
+        .iter()
+        .map(|(original, moved)| {
+            moved_to
+                .replacen("{}", &date_time(*original, locale), 1)
+                .replacen("{}", &date_time(*moved, locale), 1)
+        })
+        .collect();
+    if !moved.is_empty() {
+        description += &format!(", {} {}", with, list(&moved, locale));
+    }
+    description
+}
+
+macro_rules! describe {
+    ($($day:ty),*) => {$(
+        impl Recurrence<$day> {
+            /// Describes in plain English, e.g. `Every weekday at 9:00 AM, starting March 1,
+            /// 2021`.
+            pub fn describe(&self) -> String {
+                describe(self, Locale::En)
+            }
+
+            /// Describes in the language of `locale`, e.g. `Du lundi au vendredi à 09:00` in
+            /// French.
+            pub fn describe_in(&self, locale: Locale) -> String {
+                describe(self, locale)
+            }
+        }
+
+        impl Schedule<$day> {
+            /// Describes in plain English, exceptions included.
+            pub fn describe(&self) -> String {
+                describe_schedule(self, Locale::En)
+            }
+
+            /// Describes in the language of `locale`, exceptions included.
+            pub fn describe_in(&self, locale: Locale) -> String {
+                describe_schedule(self, locale)
+            }
+        }
+    )*};
+}
+
+describe!(OrderedWeekday, DayOfMonth, NthWeekday, DayOfYear);
+
+impl Cron {
+    /// Describes in plain English, see [`Recurrence::describe`].
+    pub fn describe(&self) -> String {
+        self.describe_in(Locale::En)
+    }
+
+    /// Describes in the language of `locale`, see [`Recurrence::describe_in`].
+    pub fn describe_in(&self, locale: Locale) -> String {
+        match self {
+            Cron::Weekly(recurrence) => recurrence.describe_in(locale),
+            Cron::Monthly(recurrence) => recurrence.describe_in(locale),
+            Cron::Yearly(recurrence) => recurrence.describe_in(locale),
+        }
+    }
+}
+
+/// `1st`, `le dernier`, `vorletzten`... business day.
+fn nth_business_day(n: i64, locale: Locale) -> String {
+    match (locale, n) {
+        (Locale::En, n) => nth(n),
+        (Locale::Fr, -1) => "le dernier".to_string(),
+        (Locale::Fr, -2) => "l'avant-dernier".to_string(),
+        (Locale::De, -1) => "letzten".to_string(),
+        (Locale::De, -2) => "vorletzten".to_string(),
+        (Locale::De, n) if n < 0 => format!("{}letzten", german_stem(-n)),
+        (Locale::De, n) => ordinal(n, locale),
+        (Locale::Es, -1) => "el último".to_string(),
+        (Locale::Es, -2) => "el penúltimo".to_string(),
+        (_, n) => format!(
+            "{} {}",
+            if locale == Locale::Fr { "le" } else { "el" },
+            ordinal(n.abs(), locale)
+        ),
+    }
+}
+
+impl<C: HolidayCalendar> BusinessDays<C> {
+    /// Describes in plain English, e.g. `On the 1st and last business days of every month at
+    /// 9:00 AM`.
+    pub fn describe(&self) -> String {
+        self.describe_in(Locale::En)
+    }
+
+    /// Describes in the language of `locale`, see [`Recurrence::describe_in`].
+    pub fn describe_in(&self, locale: Locale) -> String {
+        let mut days: Vec<_> = self.days.iter().map(|day| *day as i64).collect();
+        days.sort_by_key(|day| from_start_first(*day));
+        let (noun, from_end) = match locale {
+            Locale::En if days.len() == 1 => ("business day", ""),
+            Locale::En => ("business days", ""),
+            Locale::Fr => ("jour ouvré", " en partant de la fin"),
+            Locale::De => ("Werktag", ""),
+            Locale::Es => ("día hábil", " contando desde el final"),
+        };
+        // counted from far from the end, each day needs its own noun
+        let own_noun = !from_end.is_empty() && days.iter().any(|day| *day < -2);
+        let days: Vec<_> = days
+            .into_iter()
+            .map(|day| match (own_noun, day < -2) {
+                (false, _) => nth_business_day(day, locale),
+                (true, false) => format!("{} {}", nth_business_day(day, locale), noun),
+                (true, true) => format!("{} {}{}", nth_business_day(day, locale), noun, from_end),
+            })
+            .collect();
+        let days = list(&days, locale);
+        let days = if own_noun {
+            days
+        } else {
+            format!("{} {}", days, noun)
+        };
+        let days = match locale {
+            Locale::En => format!("On the {} of every month", days),
+            Locale::Fr => format!("{} de chaque mois", days),
+            Locale::De => format!("Am {} jedes Monats", days),
+            Locale::Es => format!("{} de cada mes", days),
+        };
+        capitalize(&days)
+            + &times(&self.times, locale)
+            + &bounds(self.start(), self.until(), self.count(), locale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BusinessDays, Cron, DayOfYear, Locale, MissingDay, OrderedWeekday, Recurrence, Schedule,
+        WeekStart,
+    };
+    use chrono::{Duration, NaiveDate, NaiveTime, Weekday};
+
+    fn hm(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn weekly() {
+        let describe = |days: &[Weekday]| Recurrence::new(days, hm(9, 0)).unwrap().describe();
+        assert_eq!(
+            describe(&[Weekday::Mon, Weekday::Fri]),
+            "Every Monday and Friday at 9:00 AM"
+        );
+        assert_eq!(
+            describe(&[
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri
+            ]),
+            "Every weekday at 9:00 AM"
+        );
+        let week = [
+            Weekday::Sun,
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+        ];
+        assert_eq!(describe(&week), "Every day at 9:00 AM");
+        assert_eq!(
+            describe(&week[..3]),
+            "Every Monday, Tuesday and Sunday at 9:00 AM"
+        );
+        assert_eq!(
+            Recurrence::new(&week[..3], hm(9, 0))
+                .unwrap()
+                .with_week_start(WeekStart::SUNDAY)
+                .describe(),
+            "Every Sunday, Monday and Tuesday at 9:00 AM"
+        );
+
+        let anchor = NaiveDate::from_ymd_opt(2021, 3, 1).unwrap();
+        let recurrence = Recurrence::new(Weekday::Fri, (hm(12, 0), hm(0, 30)))
+            .unwrap()
+            .with_interval(2, anchor)
+            .unwrap();
+        assert_eq!(
+            recurrence.describe(),
+            "Every other Friday at 12:30 AM and 12:00 PM"
+        );
+        let recurrence = Recurrence::new((Weekday::Mon, Weekday::Fri), hm(9, 0))
+            .unwrap()
+            .with_interval(2, anchor)
+            .unwrap();
+        assert_eq!(
+            recurrence.describe(),
+            "Every other week on Monday and Friday at 9:00 AM"
+        );
+        let recurrence = Recurrence::every(
+            (Weekday::Mon, Weekday::Wed),
+            hm(8, 0),
+            hm(18, 0),
+            Duration::minutes(15),
+        )
+        .unwrap()
+        .with_interval(3, anchor)
+        .unwrap()
+        .with_start(anchor.and_time(hm(8, 0)))
+        .with_count(10)
+        .unwrap();
+        assert_eq!(
+            recurrence.describe(),
+            "Every 3 weeks on Monday and Wednesday, every 15 minutes from 8:00 AM to 6:00 \
+             PM, starting March 1, 2021, 10 times"
+        );
+    }
+
+    #[test]
+    fn other_days() {
+        let recurrence = Recurrence::monthly(&[1, 15, -1], hm(17, 30), MissingDay::Skip).unwrap();
+        assert_eq!(
+            recurrence.describe(),
+            "On the 1st, 15th and last day of every month at 5:30 PM"
+        );
+        let recurrence = Recurrence::monthly(&[31], hm(9, 0), MissingDay::Clamp).unwrap();
+        assert_eq!(
+            recurrence.describe(),
+            "On the 31st (or the last day of shorter months) of every month at 9:00 AM"
+        );
+        assert_eq!(
+            recurrence.describe_in(Locale::Fr),
+            "Le 31 (ou le dernier jour des mois plus courts) de chaque mois à 09:00"
+        );
+        let recurrence = Recurrence::monthly(&[15, 30], hm(9, 0), MissingDay::Rollover).unwrap();
+        assert_eq!(
+            recurrence.describe(),
+            "On the 15th and 30th (or the 1st of the next month) of every month at 9:00 AM"
+        );
+        let recurrence = Recurrence::yearly(
+            &[DayOfYear::date(2, 29, MissingDay::Clamp).unwrap()],
+            hm(9, 0),
+        )
+        .unwrap();
+        assert_eq!(
+            recurrence.describe(),
+            "On February 29 (or February 28 in other years) every year at 9:00 AM"
+        );
+        let recurrence =
+            Recurrence::monthly_nth(&[(1, Weekday::Mon), (-2, Weekday::Fri)], hm(9, 0))
+                .unwrap()
+                .with_until(
+                    NaiveDate::from_ymd_opt(2021, 6, 30)
+                        .unwrap()
+                        .and_hms_opt(23, 59, 59)
+                        .unwrap(),
+                );
+        assert_eq!(
+            recurrence.describe(),
+            "On the first Monday and second to last Friday of every month at 9:00 AM, until June \
+             30, 2021"
+        );
+        let recurrence = Recurrence::yearly(
+            &[
+                DayOfYear::nth_weekday(11, 4, Weekday::Thu).unwrap(),
+                DayOfYear::date(12, 25, MissingDay::Skip).unwrap(),
+            ],
+            hm(12, 0),
+        )
+        .unwrap();
+        assert_eq!(
+            recurrence.describe(),
+            "On the fourth Thursday of November and December 25 every year at 12:00 PM"
+        );
+        assert_eq!(
+            "*/15 8-17 * * 1-5".parse::<Cron>().unwrap().describe(),
+            "Every weekday, every 15 minutes from 8:00 AM to 5:45 PM"
+        );
+        assert_eq!(
+            BusinessDays::weekdays(&[1, -1], hm(9, 0))
+                .unwrap()
+                .describe(),
+            "On the 1st and last business days of every month at 9:00 AM"
+        );
+    }
+
+    #[test]
+    fn schedule() {
+        let recurrence: Recurrence<OrderedWeekday> =
+            Recurrence::new(Weekday::Wed, hm(9, 0)).unwrap();
+        let mut schedule = Schedule::new(recurrence);
+        let at = |day| {
+            NaiveDate::from_ymd_opt(2020, 9, day)
+                .unwrap()
+                .and_time(hm(9, 0))
+        };
+        schedule.exclude(at(16));
+        schedule.exclude_date(NaiveDate::from_ymd_opt(2020, 9, 2).unwrap());
+        schedule.add(at(5));
+        schedule.reschedule(at(9), at(10)).unwrap();
+        assert_eq!(
+            schedule.describe(),
+            "Every Wednesday at 9:00 AM, except on September 2, 2020 and September 16, 2020 at \
+             9:00 AM, also on September 5, 2020 at 9:00 AM, with September 9, 2020 at 9:00 AM \
+             moved to September 10, 2020 at 9:00 AM"
+        );
+    }
+
+    #[test]
+    fn locales() {
+        let anchor = NaiveDate::from_ymd_opt(2021, 3, 1).unwrap();
+        let weekdays = (
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        );
+        let recurrence = Recurrence::new(weekdays, hm(9, 0))
+            .unwrap()
+            .with_start(anchor.and_time(hm(9, 0)))
+            .with_count(10)
+            .unwrap();
+        let mut schedule = Schedule::new(recurrence);
+        schedule.exclude_date(NaiveDate::from_ymd_opt(2021, 3, 2).unwrap());
+        assert_eq!(
+            schedule.describe_in(Locale::Fr),
+            "Du lundi au vendredi à 09:00, à partir du 1er mars 2021, 10 fois, sauf le 2 mars 2021"
+        );
+        assert_eq!(
+            schedule.describe_in(Locale::De),
+            "Von Montag bis Freitag um 09:00, ab 1. März 2021, 10-mal, außer am 2. März 2021"
+        );
+        assert_eq!(
+            schedule.describe_in(Locale::Es),
+            "De lunes a viernes a las 09:00, a partir del 1 de marzo de 2021, 10 veces, excepto \
+             el 2 de marzo de 2021"
+        );
+
+        let recurrence = Recurrence::new(Weekday::Sat, hm(9, 0))
+            .unwrap()
+            .with_interval(2, anchor)
+            .unwrap();
+        assert_eq!(
+            recurrence.describe_in(Locale::Fr),
+            "Un samedi sur deux à 09:00"
+        );
+        assert_eq!(
+            recurrence.describe_in(Locale::De),
+            "Jeden zweiten Samstag um 09:00"
+        );
+        assert_eq!(
+            recurrence.describe_in(Locale::Es),
+            "Cada dos sábados a las 09:00"
+        );
+
+        let recurrence =
+            Recurrence::monthly_nth(&[(1, Weekday::Mon), (-2, Weekday::Fri)], hm(9, 0)).unwrap();
+        assert_eq!(
+            recurrence.describe_in(Locale::Fr),
+            "Le premier lundi et l'avant-dernier vendredi de chaque mois à 09:00"
+        );
+        assert_eq!(
+            recurrence.describe_in(Locale::De),
+            "Am ersten Montag und vorletzten Freitag jedes Monats um 09:00"
+        );
+        let recurrence = Recurrence::monthly(&[-3], hm(9, 0), MissingDay::Skip).unwrap();
+        assert_eq!(
+            recurrence.describe_in(Locale::De),
+            "Am drittletzten Tag jedes Monats um 09:00"
+        );
+        assert_eq!(
+            BusinessDays::weekdays(&[-3], hm(9, 0))
+                .unwrap()
+                .describe_in(Locale::De),
+            "Am drittletzten Werktag jedes Monats um 09:00"
+        );
+        let recurrence = Recurrence::yearly(
+            &[
+                DayOfYear::nth_weekday(4, 4, Weekday::Thu).unwrap(),
+                DayOfYear::date(12, 1, MissingDay::Skip).unwrap(),
+            ],
+            hm(12, 0),
+        )
+        .unwrap();
+        assert_eq!(
+            recurrence.describe_in(Locale::Fr),
+            "Le quatrième jeudi d'avril et le 1er décembre, chaque année à 12:00"
+        );
+        assert_eq!(
+            BusinessDays::weekdays(&[1, -1], hm(9, 0))
+                .unwrap()
+                .describe_in(Locale::Es),
+            "El 1.er y el último día hábil de cada mes a las 09:00"
+        );
+    }
+}
@@ -0,0 +1,284 @@
+use chrono::{DateTime, Duration, LocalResult, NaiveDateTime, Offset, TimeZone};
+
+/// What to do with an occurrence whose wall time is skipped by a daylight saving time change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Gap {
+    /// No occurrence.
+    Skip,
+    /// Shift the occurrence forward by the length of the gap, e.g. 02:30 becomes 03:30 when
+    /// clocks go from 02:00 to 03:00.
+    #[default]
+    Shift,
+}
+
+/// Which instant to pick for an occurrence whose wall time happens twice, when clocks go back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Fold {
+    /// The first one, before clocks go back.
+    #[default]
+    Earliest,
+    /// The second one, after clocks went back.
+    Latest,
+}
+
+/// The instant a wall time happens at in `tz`, if any.
+pub(crate) fn resolve<T: TimeZone>(
+    tz: &T,
+    local: NaiveDateTime,
+    gap: Gap,
+    fold: Fold,
+) -> Option<DateTime<T>> {
+    match tz.from_local_datetime(&local) {
+        LocalResult::Single(date) => Some(date),
+        LocalResult::Ambiguous(earliest, latest) => Some(match fold {
+            Fold::Earliest => earliest,
+            Fold::Latest => latest,
+        }),
+        LocalResult::None => match gap {
+            Gap::Skip => None,
+            Gap::Shift => {
+                // when the wall clock would have shown `local` without the change
+                let before = tz
+                    .from_local_datetime(&(local - Duration::days(1)))
+                    .earliest()?;
+                let offset = before.offset().fix().local_minus_utc();
+                Some(tz.from_utc_datetime(&(local - Duration::seconds(offset as i64))))
+            }
+        },
+    }
+}
+
+/// First instant strictly after `date` of the wall times `next_local` walks through, from `start`
+/// on and up to `end`.
+pub(crate) fn next<T: TimeZone>(
+    date: &DateTime<T>,
+    (gap, fold): (Gap, Fold),
+    start: Option<NaiveDateTime>,
+    end: Option<NaiveDateTime>,
+    next_local: impl Fn(NaiveDateTime) -> Option<NaiveDateTime>,
+) -> Option<DateTime<T>> {
+    let tz = date.timezone();
+    let mut local = date.naive_local() - slack_after(date);
+    if let Some(start) = start {
+        local = local.max(start - Duration::nanoseconds(1));
+    }
+    loop {
+        local = next_local(local)?;
+        if matches!(end, Some(end) if local > end) {
+            return None;
+        }
+        match resolve(&tz, local, gap, fold) {
+            Some(next) if next > *date => return Some(next),
+            _ => continue,
+        }
+    }
+}
+
+/// Last instant strictly before `date`, see [`next`].
+pub(crate) fn prev<T: TimeZone>(
+    date: &DateTime<T>,
+    (gap, fold): (Gap, Fold),
+    start: Option<NaiveDateTime>,
+    end: Option<NaiveDateTime>,
+    prev_local: impl Fn(NaiveDateTime) -> Option<NaiveDateTime>,
+) -> Option<DateTime<T>> {
+    let tz = date.timezone();
+    let mut local = date.naive_local() + slack_before(date);
+    if let Some(end) = end {
+        local = local.min(end + Duration::nanoseconds(1));
+    }
+    loop {
+        local = prev_local(local)?;
+        if matches!(start, Some(start) if local < start) {
+            return None;
+        }
+        match resolve(&tz, local, gap, fold) {
+            Some(prev) if prev < *date => return Some(prev),
+            _ => continue,
+        }
+    }
+}
+
+fn offset<T: TimeZone>(date: &DateTime<T>) -> i64 {
+    date.offset().fix().local_minus_utc() as i64
+}
+
+/// How far before `date`'s wall time an occurrence may be and still happen after `date`, which is
+/// when a change of offset right around `date` replays or shifts wall times.
+pub(crate) fn slack_after<T: TimeZone>(date: &DateTime<T>) -> Duration {
+    let day = Duration::days(1);
+    let around = offset(&(date.clone() - day)).min(offset(&(date.clone() + day)));
+    Duration::seconds((offset(date) - around).max(0))
+}
+
+/// How far after `date`'s wall time an occurrence may be and still happen before `date`.
+pub(crate) fn slack_before<T: TimeZone>(date: &DateTime<T>) -> Duration {
+    let day = Duration::days(1);
+    let around = offset(&(date.clone() - day)).max(offset(&(date.clone() + day)));
+    Duration::seconds((around - offset(date)).max(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Fold, Gap, OrderedWeekday, Recurrence, Recurrent};
+    use chrono::{DateTime, NaiveTime, TimeZone, Utc, Weekday};
+    use chrono_tz::America::New_York;
+    use chrono_tz::Australia::Lord_Howe;
+    use chrono_tz::Europe::Paris;
+    use chrono_tz::Tz;
+
+    fn parse(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn daily(hour: u32, minute: u32) -> Recurrence<OrderedWeekday> {
+        let days = (
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        );
+        Recurrence::new(days, NaiveTime::from_hms_opt(hour, minute, 0).unwrap()).unwrap()
+    }
+
+    fn next(recurrence: &Recurrence<OrderedWeekday>, tz: Tz, now: &str) -> DateTime<Utc> {
+        recurrence
+            .next(&parse(now).with_timezone(&tz))
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn prev(recurrence: &Recurrence<OrderedWeekday>, tz: Tz, now: &str) -> DateTime<Utc> {
+        recurrence
+            .prev(&parse(now).with_timezone(&tz))
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn spring_forward() {
+        // 02:30 doesn't exist on 2021-03-28 in Paris
+        let recurrence = daily(2, 30);
+        assert_eq!(
+            next(&recurrence, Paris, "2021-03-27T12:00:00Z"),
+            parse("2021-03-28T01:30:00Z")
+        );
+        assert_eq!(
+            prev(&recurrence, Paris, "2021-03-28T12:00:00Z"),
+            parse("2021-03-28T01:30:00Z")
+        );
+        assert_eq!(
+            next(
+                &recurrence.clone().with_gap(Gap::Skip),
+                Paris,
+                "2021-03-27T12:00:00Z"
+            ),
+            parse("2021-03-29T00:30:00Z")
+        );
+        assert_eq!(
+            prev(
+                &recurrence.with_gap(Gap::Skip),
+                Paris,
+                "2021-03-28T12:00:00Z"
+            ),
+            parse("2021-03-27T01:30:00Z")
+        );
+
+        // 02:15 doesn't exist on 2021-03-14 in New York
+        let recurrence = daily(2, 15);
+        assert_eq!(
+            next(&recurrence, New_York, "2021-03-14T00:00:00Z"),
+            parse("2021-03-14T07:15:00Z")
+        );
+        assert_eq!(
+            next(&recurrence, New_York, "2021-03-14T07:15:00Z"),
+            parse("2021-03-15T06:15:00Z")
+        );
+
+        // clocks go from 02:00 to 02:30 on 2021-10-03 in Lord Howe
+        let recurrence = daily(2, 10);
+        assert_eq!(
+            next(&recurrence, Lord_Howe, "2021-10-02T00:00:00Z"),
+            parse("2021-10-02T15:40:00Z")
+        );
+    }
+
+    #[test]
+    fn fall_back() {
+        // 02:30 happens twice on 2021-10-31 in Paris
+        let recurrence = daily(2, 30);
+        assert_eq!(
+            next(&recurrence, Paris, "2021-10-30T12:00:00Z"),
+            parse("2021-10-31T00:30:00Z")
+        );
+        // happens once only, even when asked in between
+        assert_eq!(
+            next(&recurrence, Paris, "2021-10-31T00:45:00Z"),
+            parse("2021-11-01T01:30:00Z")
+        );
+        let latest = recurrence.with_fold(Fold::Latest);
+        assert_eq!(
+            next(&latest, Paris, "2021-10-30T12:00:00Z"),
+            parse("2021-10-31T01:30:00Z")
+        );
+        assert_eq!(
+            prev(&latest, Paris, "2021-10-31T01:45:00Z"),
+            parse("2021-10-31T01:30:00Z")
+        );
+        assert_eq!(
+            prev(&latest, Paris, "2021-10-31T01:15:00Z"),
+            parse("2021-10-30T00:30:00Z")
+        );
+
+        // 01:45 happens twice on 2021-11-07 in New York
+        let recurrence = daily(1, 45);
+        assert_eq!(
+            next(&recurrence, New_York, "2021-11-07T05:50:00Z"),
+            parse("2021-11-08T06:45:00Z")
+        );
+        assert_eq!(
+            prev(&recurrence, New_York, "2021-11-08T00:00:00Z"),
+            parse("2021-11-07T05:45:00Z")
+        );
+
+        // clocks go from 02:00 back to 01:30 on 2021-04-04 in Lord Howe
+        let latest = daily(1, 40).with_fold(Fold::Latest);
+        assert_eq!(
+            next(&latest, Lord_Howe, "2021-04-03T12:00:00Z"),
+            parse("2021-04-03T15:10:00Z")
+        );
+    }
+
+    #[test]
+    fn every_across_changes() {
+        let recurrence = Recurrence::every(
+            Weekday::Sun,
+            NaiveTime::from_hms_opt(1, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(4, 0, 0).unwrap(),
+            chrono::Duration::hours(1),
+        )
+        .unwrap();
+        let start = Paris.with_ymd_and_hms(2021, 3, 28, 0, 0, 0).unwrap();
+        // 02:00 shifts to 03:00, which already is an occurrence
+        assert_eq!(
+            recurrence
+                .occurrences_after(&start)
+                .take(4)
+                .map(|date| date.with_timezone(&Utc))
+                .collect::<Vec<_>>(),
+            vec![
+                parse("2021-03-28T00:00:00Z"),
+                parse("2021-03-28T01:00:00Z"),
+                parse("2021-03-28T02:00:00Z"),
+                parse("2021-04-03T23:00:00Z"),
+            ]
+        );
+    }
+}
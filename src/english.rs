@@ -0,0 +1,933 @@
+use crate::locale::{normalize, WEEKDAYS};
+use crate::times::DayTimes;
+use crate::{Error, Locale, OrderedWeekday, Recurrence};
+use chrono::{Duration, NaiveDate, NaiveTime};
+use std::collections::BTreeSet;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Kind {
+    /// Lowercased, without dots: `a.m.` is `am`.
+    Word(String),
+    Number(u32),
+    Symbol(char),
+}
+
+#[derive(Clone, Debug)]
+struct Token<'a> {
+    kind: Kind,
+    position: usize,
+    text: &'a str,
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token<'_>>, Error> {
+    let mut tokens = vec![];
+    let mut chars = s.char_indices().peekable();
+    while let Some((position, c)) = chars.next() {
+        let mut end = position + c.len_utf8();
+        let mut take_while = |f: fn(char) -> bool| {
+            while let Some((index, c)) = chars.peek().copied() {
+                if !f(c) {
+                    break;
+                }
+                end = index + c.len_utf8();
+                chars.next();
+            }
+            end
+        };
+        let kind = if c.is_whitespace() {
+            continue;
+        } else if c.is_ascii_digit() {
+            let end = take_while(|c| c.is_ascii_digit());
+            match s[position..end].parse() {
+                Ok(number) => Kind::Number(number),
+                Err(_) => {
+                    return Err(Error::UnexpectedToken {
+                        position,
+                        expected: "a smaller number".to_string(),
+                        found: s[position..end].to_string(),
+                    })
+                }
+            }
+        } else if c.is_alphabetic() {
+            let end = take_while(|c| c.is_alphabetic() || c == '.' || c == '\'');
+            Kind::Word(s[position..end].to_lowercase().replace('.', ""))
+        } else {
+            Kind::Symbol(c)
+        };
+        tokens.push(Token {
+            kind,
+            position,
+            text: &s[position..end],
+        });
+    }
+    Ok(tokens)
+}
+
+/// Index of the name `word` starts, when at least `min` letters long.
+fn prefix(names: &[&str], word: &str, min: usize) -> Option<usize> {
+    let word = normalize(word);
+    names
+        .iter()
+        .position(|name| word.len() >= min && normalize(name).starts_with(&word))
+}
+
+/// `mon`, `Monday`, `tues`, `Fridays`, `lundis` in French, `Mo` in German...
+fn weekday(locale: Locale, word: &str) -> Option<OrderedWeekday> {
+    let names = locale.names();
+    let find = |word: &str| {
+        let abbreviation = normalize(word);
+        names
+            .short_weekdays
+            .iter()
+            .position(|short| normalize(short) == abbreviation)
+            .or_else(|| prefix(&names.weekdays, word, 3))
+    };
+    let plural = || {
+        word.strip_suffix('s')
+            .filter(|stem| stem.chars().count() >= 3)
+    };
+    find(word)
+        .or_else(|| find(plural()?))
+        .map(|index| WEEKDAYS[index])
+}
+
+/// `mar`, `March`, `juil` in French...
+fn month(locale: Locale, word: &str) -> Option<u32> {
+    prefix(&locale.names().months, word, 3).map(|index| index as u32 + 1)
+}
+
+/// Phrases of the language of `locale` and the English words they stand for, longest first.
+fn phrases(locale: Locale) -> &'static [(&'static [&'static str], &'static [&'static str])] {
+    match locale {
+        Locale::En => &[],
+        Locale::Fr => &[
+            (
+                &["une", "semaine", "sur", "deux"],
+                &["every", "other", "week"],
+            ),
+            // `un samedi sur deux`
+            (&["sur", "deux"], &["of", "every", "other", "week"]),
+            (&["a", "partir", "du"], &["starting"]),
+            (&["a", "partir", "de"], &["starting"]),
+            (&["tous", "les"], &["every"]),
+            (&["toutes", "les"], &["every"]),
+            (&["une", "fois"], &["once"]),
+            (&["jusqu'au"], &["until"]),
+            (&["jusqu'a"], &["until"]),
+            (&["chaque"], &["every"]),
+            (&["un"], &[]),
+            (&["du"], &["from"]),
+            (&["de"], &["from"]),
+            (&["au"], &["to"]),
+            (&["a"], &["at"]),
+            (&["et"], &["and"]),
+            (&["le"], &[]),
+            (&["les"], &[]),
+            (&["jour"], &["day"]),
+            (&["jours"], &["days"]),
+            (&["semaine"], &["week"]),
+            (&["semaines"], &["weeks"]),
+            (&["midi"], &["noon"]),
+            (&["minuit"], &["midnight"]),
+            (&["heure"], &["hour"]),
+            (&["heures"], &["hours"]),
+            (&["secondes"], &["seconds"]),
+            (&["fois"], &["times"]),
+        ],
+        Locale::De => &[
+            (&["jede", "zweite", "woche"], &["every", "other", "week"]),
+            (&["jeden", "zweiten"], &["every", "other"]),
+            (&["jeden"], &["every"]),
+            (&["jede"], &["every"]),
+            (&["alle"], &["every"]),
+            (&["von"], &["from"]),
+            (&["bis"], &["through"]),
+            (&["ab"], &["starting"]),
+            (&["um"], &["at"]),
+            (&["und"], &["and"]),
+            (&["am"], &[]),
+            (&["uhr"], &[]),
+            (&["tag"], &["day"]),
+            (&["tage"], &["days"]),
+            (&["taglich"], &["daily"]),
+            (&["woche"], &["week"]),
+            (&["wochen"], &["weeks"]),
+            (&["mittag"], &["noon"]),
+            (&["mittags"], &["noon"]),
+            (&["mitternacht"], &["midnight"]),
+            (&["stunde"], &["hour"]),
+            (&["stunden"], &["hours"]),
+            (&["minute"], &["minute"]),
+            (&["minuten"], &["minutes"]),
+            (&["sekunden"], &["seconds"]),
+            (&["einmal"], &["once"]),
+            (&["mal"], &["times"]),
+        ],
+        Locale::Es => &[
+            (&["cada", "dos", "semanas"], &["every", "other", "week"]),
+            (&["cada", "dos"], &["every", "other"]),
+            (&["a", "partir", "del"], &["starting"]),
+            (&["a", "partir", "de"], &["starting"]),
+            (&["todos", "los"], &["every"]),
+            (&["a", "las"], &["at"]),
+            (&["a", "la"], &["at"]),
+            (&["una", "vez"], &["once"]),
+            (&["cada"], &["every"]),
+            (&["de"], &["from"]),
+            (&["desde"], &["starting"]),
+            (&["hasta"], &["until"]),
+            (&["a"], &["at"]),
+            (&["y"], &["and"]),
+            (&["el"], &[]),
+            (&["los"], &[]),
+            (&["dia"], &["day"]),
+            (&["dias"], &["days"]),
+            (&["diario"], &["daily"]),
+            (&["semana"], &["week"]),
+            (&["semanas"], &["weeks"]),
+            (&["mediodia"], &["noon"]),
+            (&["medianoche"], &["midnight"]),
+            (&["hora"], &["hour"]),
+            (&["horas"], &["hours"]),
+            (&["minutos"], &["minutes"]),
+            (&["segundos"], &["seconds"]),
+            (&["veces"], &["times"]),
+        ],
+    }
+}
+
+/// Rewrites the phrases of `locale` into the English words the parser understands, keeping the
+/// position and text of the first token for errors.
+fn translate(tokens: Vec<Token<'_>>, locale: Locale) -> Vec<Token<'_>> {
+    let is = |token: Option<&Token>, word: &str| matches!(token, Some(Token { kind: Kind::Word(found), .. }) if normalize(found) == word);
+    let mut translated = vec![];
+    let mut index = 0;
+    while index < tokens.len() {
+        let found = phrases(locale).iter().find(|(phrase, _)| {
+            phrase
+                .iter()
+                .enumerate()
+                .all(|(offset, word)| is(tokens.get(index + offset), word))
+        });
+        let (phrase, words) = match found {
+            Some(found) => found,
+            None => {
+                translated.push(tokens[index].clone());
+                index += 1;
+                continue;
+            }
+        };
+        index += phrase.len();
+        // `a` is `to` between days: `de lunes a viernes`
+        let before_day = matches!(
+            tokens.get(index),
+            Some(Token { kind: Kind::Word(next), .. }) if weekday(locale, next).is_some()
+        );
+        for word in words.iter() {
+            let word = if *word == "at" && before_day {
+                "to"
+            } else {
+                word
+            };
+            translated.push(Token {
+                kind: Kind::Word(word.to_string()),
+                ..tokens[index - phrase.len()].clone()
+            });
+        }
+    }
+    translated
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    index: usize,
+    len: usize,
+    locale: Locale,
+}
+
+impl<'a> Parser<'a> {
+    fn peek_at(&self, offset: usize) -> Option<&Kind> {
+        self.tokens
+            .get(self.index + offset)
+            .map(|token| &token.kind)
+    }
+
+    fn peek(&self) -> Option<&Kind> {
+        self.peek_at(0)
+    }
+
+    fn peek_word(&self, offset: usize, words: &[&str]) -> bool {
+        matches!(self.peek_at(offset), Some(Kind::Word(word)) if words.contains(&word.as_str()))
+    }
+
+    /// Consumes the next token if it is one of `words`.
+    fn word(&mut self, words: &[&str]) -> bool {
+        let found = self.peek_word(0, words);
+        if found {
+            self.index += 1;
+        }
+        found
+    }
+
+    /// Consumes the next token if it is `symbol`.
+    fn symbol(&mut self, symbol: char) -> bool {
+        let found = self.peek() == Some(&Kind::Symbol(symbol));
+        if found {
+            self.index += 1;
+        }
+        found
+    }
+
+    /// Consumes a list separator: `,`, `and` or `&`.
+    fn separator(&mut self) -> bool {
+        self.symbol(',') || self.symbol('&') || self.word(&["and"])
+    }
+
+    /// An error about the next token, which isn't what was `expected`.
+    fn error(&self, expected: &str) -> Error {
+        match self.tokens.get(self.index) {
+            Some(token) => Error::UnexpectedToken {
+                position: token.position,
+                expected: expected.to_string(),
+                found: token.text.to_string(),
+            },
+            None => Error::UnexpectedEnd {
+                position: self.len,
+                expected: expected.to_string(),
+            },
+        }
+    }
+
+    fn number(&mut self, expected: &str) -> Result<u32, Error> {
+        match self.peek() {
+            Some(Kind::Number(number)) => {
+                let number = *number;
+                self.index += 1;
+                Ok(number)
+            }
+            _ => Err(self.error(expected)),
+        }
+    }
+
+    /// `every other`, `every 3 weeks`... as an interval, leaving anything else alone.
+    fn interval(&mut self) -> Result<Option<u32>, Error> {
+        if !self.peek_word(0, &["every", "each"]) {
+            return Ok(None);
+        }
+        match self.peek_at(1) {
+            Some(Kind::Word(word)) if word == "other" => {
+                self.index += 2;
+                if self.word(&["week"]) {
+                    self.word(&["on"]);
+                }
+                Ok(Some(2))
+            }
+            Some(Kind::Number(every)) if self.peek_word(2, &["weeks", "week"]) => {
+                let (every, position) = (*every, self.tokens[self.index + 1].position);
+                self.index += 3;
+                self.word(&["on"]);
+                if every == 0 {
+                    return Err(Error::UnexpectedToken {
+                        position,
+                        expected: "a number of weeks".to_string(),
+                        found: "0".to_string(),
+                    });
+                }
+                Ok(Some(every))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// `every other friday`, `mon-fri`, `weekdays and sunday of every 2 weeks`...
+    fn days(&mut self) -> Result<(BTreeSet<OrderedWeekday>, Option<u32>), Error> {
+        let mut interval = self.interval()?;
+        self.word(&["every", "each", "on", "from"]);
+        let mut days = BTreeSet::new();
+        loop {
+            days.extend(self.day_item()?);
+            let index = self.index;
+            if !self.separator() || !self.at_day() {
+                self.index = index;
+                break;
+            }
+        }
+        if interval.is_none() {
+            let index = self.index;
+            self.symbol(',');
+            self.word(&["of"]);
+            interval = self.interval()?;
+            if interval.is_none() {
+                self.index = index;
+            }
+        }
+        Ok((days, interval))
+    }
+
+    fn at_day(&self) -> bool {
+        matches!(self.peek(), Some(Kind::Word(word)) if weekday(self.locale, word).is_some())
+            || self.peek_word(
+                0,
+                &[
+                    "day", "days", "daily", "weekday", "weekdays", "weekend", "weekends",
+                ],
+            )
+    }
+
+    fn weekday(&mut self) -> Result<OrderedWeekday, Error> {
+        match self.peek() {
+            Some(Kind::Word(word)) => match weekday(self.locale, word) {
+                Some(weekday) => {
+                    self.index += 1;
+                    Ok(weekday)
+                }
+                None => Err(self.error("a day of week")),
+            },
+            _ => Err(self.error("a day of week")),
+        }
+    }
+
+    fn day_item(&mut self) -> Result<Vec<OrderedWeekday>, Error> {
+        let all = |from: OrderedWeekday, to: OrderedWeekday| -> Vec<OrderedWeekday> {
+            let (from, to) = (from as usize, to as usize);
+            let len = (to + 7 - from) % 7 + 1;
+            (0..len)
+                .map(|offset| WEEKDAYS[(from + offset) % 7])
+                .collect()
+        };
+        if self.word(&["day", "days", "daily"]) {
+            return Ok(all(OrderedWeekday::Mon, OrderedWeekday::Sun));
+        }
+        if self.word(&["weekday", "weekdays"]) {
+            return Ok(all(OrderedWeekday::Mon, OrderedWeekday::Fri));
+        }
+        if self.word(&["weekend", "weekends"]) {
+            return Ok(all(OrderedWeekday::Sat, OrderedWeekday::Sun));
+        }
+        let from = self.weekday()?;
+        if self.symbol('-') || self.word(&["to", "through", "thru"]) {
+            let to = self.weekday()?;
+            return Ok(all(from, to));
+        }
+        Ok(vec![from])
+    }
+
+    fn at_times(&self) -> bool {
+        let count = self.peek_word(1, &["times", "occurrences"]);
+        matches!(self.peek(), Some(Kind::Number(_)) if !count)
+            || self.peek_word(0, &["at", "noon", "midnight"])
+            || self.peek() == Some(&Kind::Symbol('@'))
+            || (self.peek_word(0, &["every", "each"]) && self.at_step(1))
+    }
+
+    /// Whether a step, e.g. `15 minutes` or `hour`, is at `offset`.
+    fn at_step(&self, offset: usize) -> bool {
+        let unit = match self.peek_at(offset) {
+            Some(Kind::Number(_)) => offset + 1,
+            _ => offset,
+        };
+        self.peek_word(unit, UNITS)
+    }
+
+    /// `at 9am and 5:30 pm`, `every 15 minutes from 8am to 6pm`...
+    fn times(&mut self) -> Result<DayTimes, Error> {
+        if self.word(&["every", "each"]) {
+            let count = match self.peek() {
+                Some(Kind::Number(_)) => self.number("a number")?,
+                _ => 1,
+            };
+            let unit = match self.peek() {
+                Some(Kind::Word(word)) if word.starts_with('h') => 3600,
+                Some(Kind::Word(word)) if word.starts_with('m') => 60,
+                _ => 1,
+            };
+            let step_position = self.index;
+            if !self.word(UNITS) {
+                return Err(self.error("`hours`, `minutes` or `seconds`"));
+            }
+            if !self.word(&["from", "between"]) {
+                return Err(self.error("`from`"));
+            }
+            let from = self.time()?;
+            // `at` is `to` in `de 8h à 18h`
+            let to = ["to", "until", "till", "through", "and", "at"];
+            if !(self.word(&to) || self.symbol('-')) {
+                return Err(self.error("`to`"));
+            }
+            let to_position = self.index;
+            let to = self.time()?;
+            return DayTimes::every(from, to, Duration::seconds(count as i64 * unit)).map_err(
+                |error| match error {
+                    Error::InvalidWindow => {
+                        self.index = to_position;
+                        self.error("a time after the start")
+                    }
+                    _ => {
+                        self.index = step_position;
+                        self.error("a step shorter than a day")
+                    }
+                },
+            );
+        }
+        if !self.word(&["at"]) {
+            self.symbol('@');
+        }
+        let mut times = BTreeSet::new();
+        loop {
+            times.insert(self.time()?);
+            let index = self.index;
+            if !self.separator() || !self.at_times() {
+                self.index = index;
+                break;
+            }
+            self.word(&["at"]);
+        }
+        Ok(DayTimes::List(times))
+    }
+
+    /// `9`, `9am`, `17:30`, `5:30:15 p.m.`, `noon`, `17h30`...
+    fn time(&mut self) -> Result<NaiveTime, Error> {
+        if self.word(&["noon"]) {
+            return Ok(NaiveTime::from_hms_opt(12, 0, 0).unwrap());
+        }
+        if self.word(&["midnight"]) {
+            return Ok(NaiveTime::MIN);
+        }
+        let hour_index = self.index;
+        let mut hour = self.number("a time")?;
+        let mut minute = 0;
+        let mut second = 0;
+        let colon = self.symbol(':');
+        if colon || (self.word(&["h"]) && matches!(self.peek(), Some(Kind::Number(_)))) {
+            minute = self.number("minutes")?;
+            if minute > 59 {
+                self.index -= 1;
+                return Err(self.error("minutes from 00 to 59"));
+            }
+            if colon && self.symbol(':') {
+                second = self.number("seconds")?;
+                if second > 59 {
+                    self.index -= 1;
+                    return Err(self.error("seconds from 00 to 59"));
+                }
+            }
+        }
+        let half = if self.word(&["am"]) {
+            Some(0)
+        } else if self.word(&["pm"]) {
+            Some(12)
+        } else {
+            None
+        };
+        match half {
+            Some(half) if (1..=12).contains(&hour) => hour = hour % 12 + half,
+            Some(_) => {
+                self.index = hour_index;
+                return Err(self.error("an hour from 1 to 12"));
+            }
+            None if hour > 23 => {
+                self.index = hour_index;
+                return Err(self.error("an hour from 0 to 23"));
+            }
+            None => {}
+        }
+        Ok(NaiveTime::from_hms_opt(hour, minute, second).unwrap())
+    }
+
+    /// `2021-03-01`, `March 1, 2021`, `1 mar 2021`...
+    fn date(&mut self) -> Result<NaiveDate, Error> {
+        let position = self.index;
+        let (year, month, day) = match self.peek() {
+            Some(Kind::Number(_)) if self.peek_at(1) == Some(&Kind::Symbol('-')) => {
+                let year = self.number("a year")?;
+                self.symbol('-');
+                let month = self.number("a month")?;
+                if !self.symbol('-') {
+                    return Err(self.error("`-`"));
+                }
+                (year, month, self.number("a day")?)
+            }
+            Some(Kind::Number(_)) => {
+                let day = self.number("a day")?;
+                self.day_suffix();
+                let month = self.month()?;
+                if !self.symbol(',') && self.locale == Locale::Es {
+                    self.word(&["from"]);
+                }
+                (self.number("a year")?, month, day)
+            }
+            _ => {
+                let month = self.month()?;
+                let day = self.number("a day")?;
+                self.symbol(',');
+                (self.number("a year")?, month, day)
+            }
+        };
+        NaiveDate::from_ymd_opt(year as i32, month, day).ok_or_else(|| {
+            self.index = position;
+            self.error("a valid date")
+        })
+    }
+
+    /// What follows the day before its month: `1er mars`, `30. Juni`, `30 de junio`, `de` having
+    /// become `from`.
+    fn day_suffix(&mut self) {
+        match self.locale {
+            Locale::Fr => self.word(&["er"]),
+            Locale::De => self.symbol('.'),
+            Locale::Es => self.word(&["from"]),
+            Locale::En => false,
+        };
+    }
+
+    fn month(&mut self) -> Result<u32, Error> {
+        match self.peek() {
+            Some(Kind::Word(word)) => match month(self.locale, word) {
+                Some(month) => {
+                    self.index += 1;
+                    Ok(month)
+                }
+                None => Err(self.error("a month")),
+            },
+            _ => Err(self.error("a month")),
+        }
+    }
+}
+
+const UNITS: &[&str] = &[
+    "hours", "hour", "hrs", "hr", "h", "minutes", "minute", "mins", "min", "m", "seconds",
+    "second", "secs", "sec", "s",
+];
+
+fn parse(s: &str, locale: Locale, today: NaiveDate) -> Result<Recurrence<OrderedWeekday>, Error> {
+    let mut parser = Parser {
+        tokens: translate(tokenize(s)?, locale),
+        index: 0,
+        len: s.len(),
+        locale,
+    };
+    let (days, interval, times) = if parser.at_times() {
+        let times = parser.times()?;
+        parser.symbol(',');
+        let (days, interval) = parser.days()?;
+        (days, interval, times)
+    } else {
+        let (days, interval) = parser.days()?;
+        parser.symbol(',');
+        (days, interval, parser.times()?)
+    };
+
+    let (mut start, mut until, mut count) = (None, None, None);
+    while parser.index < parser.tokens.len() {
+        parser.symbol(',');
+        if parser.word(&["starting", "from", "since", "beginning"]) {
+            parser.word(&["on"]);
+            start = Some(parser.date()?);
+        } else if parser.word(&["until", "till", "through"]) {
+            until = Some(parser.date()?);
+        } else if parser.word(&["once"]) {
+            count = Some(1);
+        } else if let Some(Kind::Number(_)) = parser.peek() {
+            count = Some(parser.number("a count")?);
+            // `10-mal`
+            parser.symbol('-');
+            if !parser.word(&["times", "occurrences"]) {
+                return Err(parser.error("`times`"));
+            }
+        } else {
+            return Err(parser.error("`starting`, `until`, a count or the end"));
+        }
+    }
+
+    let mut recurrence = Recurrence::from_days(days, NaiveTime::MIN)?;
+    recurrence.times = times;
+    if let Some(interval) = interval {
+        recurrence = recurrence.with_interval(interval, start.unwrap_or(today))?;
+    }
+    if let Some(start) = start.or(count.map(|_| today)) {
+        recurrence = recurrence.with_start(start.and_time(NaiveTime::MIN));
+    }
+    if let Some(until) = until {
+        recurrence = recurrence.with_until(until.and_hms_opt(23, 59, 59).unwrap());
+    }
+    if let Some(count) = count {
+        recurrence = recurrence.with_count(count)?;
+    }
+    Ok(recurrence)
+}
+
+impl Recurrence<OrderedWeekday> {
+    /// Parses a schedule typed in English, e.g. `every weekday at 9am`, `mon,wed 17:30` or
+    /// `every other friday at noon, until 2021-06-30`.
+    ///
+    /// Intervals are in phase with the start if given, with the week of `today` otherwise, and
+    /// counts without a start count from `today`. Errors tell the position of the unexpected
+    /// token and what was expected instead.
+    pub fn parse_english(s: &str, today: NaiveDate) -> Result<Self, Error> {
+        parse(s, Locale::En, today)
+    }
+
+    /// Parses a schedule typed in the language of `locale`, e.g. `lundi à 9h`,
+    /// `von Montag bis Freitag um 8 Uhr` or `todos los días a las 9, hasta el 2021-06-30`, see
+    /// [`parse_english`](Self::parse_english).
+    ///
+    /// Weekdays and months are named in that language, and its most common phrases stand for the
+    /// English ones, which are understood too. Errors name the English words expected.
+    pub fn parse_in(s: &str, locale: Locale, today: NaiveDate) -> Result<Self, Error> {
+        parse(s, locale, today)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Locale, OrderedWeekday, Recurrence, Recurrent};
+    use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc, Weekday};
+
+    fn parse(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn today() -> NaiveDate {
+        // a thursday
+        NaiveDate::from_ymd_opt(2020, 9, 3).unwrap()
+    }
+
+    fn english(s: &str) -> Recurrence<OrderedWeekday> {
+        Recurrence::parse_english(s, today()).unwrap()
+    }
+
+    fn hm(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn weekly() {
+        let weekdays = (
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        );
+        let expected = Recurrence::new(weekdays, hm(9, 0)).unwrap();
+        for s in &[
+            "every weekday at 9am",
+            "Weekdays 9:00",
+            "mon-fri @ 9 a.m.",
+            "monday through friday at 09:00",
+            "At 9AM on weekdays",
+        ] {
+            assert_eq!(english(s), expected, "{}", s);
+        }
+        assert_eq!(
+            english("mon,wed 17:30"),
+            Recurrence::new((Weekday::Mon, Weekday::Wed), hm(17, 30)).unwrap()
+        );
+        assert_eq!(
+            english("Every weekend and Tuesdays at 8am and 12:30pm"),
+            Recurrence::new(
+                (Weekday::Tue, Weekday::Sat, Weekday::Sun),
+                (hm(8, 0), hm(12, 30))
+            )
+            .unwrap()
+        );
+        assert_eq!(english("daily at midnight"), english("every day at 12 am"));
+        assert_eq!(
+            english("weekdays every 15 minutes from 8am to 6pm"),
+            Recurrence::every(weekdays, hm(8, 0), hm(18, 0), Duration::minutes(15)).unwrap()
+        );
+    }
+
+    #[test]
+    fn intervals_and_bounds() {
+        // in phase with this week
+        let fridays = english("every other friday at noon");
+        assert_eq!(
+            fridays.next(&parse("2020-09-01T00:00:00Z")),
+            Some(parse("2020-09-04T12:00:00Z"))
+        );
+        assert_eq!(
+            fridays.next(&parse("2020-09-04T12:00:00Z")),
+            Some(parse("2020-09-18T12:00:00Z"))
+        );
+        assert_eq!(english("every 2 weeks on friday at 12pm"), fridays);
+        assert_eq!(english("fridays of every other week at noon"), fridays);
+
+        let course = english("tue 9:00, starting March 1, 2021, until 2021-06-30");
+        assert_eq!(
+            course.next(&parse("2021-01-01T00:00:00Z")),
+            Some(parse("2021-03-02T09:00:00Z"))
+        );
+        assert_eq!(
+            course.prev(&parse("2022-01-01T00:00:00Z")),
+            Some(parse("2021-06-29T09:00:00Z"))
+        );
+        let sessions = english("tuesdays at 9, 3 times");
+        assert_eq!(
+            sessions
+                .occurrences_after(&parse("2020-01-01T00:00:00Z"))
+                .collect::<Vec<_>>(),
+            vec![
+                parse("2020-09-08T09:00:00Z"),
+                parse("2020-09-15T09:00:00Z"),
+                parse("2020-09-22T09:00:00Z"),
+            ]
+        );
+    }
+
+    #[test]
+    fn locales() {
+        let parse_in = |s, locale| Recurrence::parse_in(s, locale, today()).unwrap();
+        assert_eq!(parse_in("lundi à 9h", Locale::Fr), english("monday at 9am"));
+        for (s, locale) in &[
+            ("du lundi au vendredi à 17h30", Locale::Fr),
+            ("von Montag bis Freitag um 17:30 Uhr", Locale::De),
+            ("de lunes a viernes a las 17:30", Locale::Es),
+        ] {
+            assert_eq!(parse_in(s, *locale), english("weekdays at 5:30pm"), "{}", s);
+        }
+        assert_eq!(
+            parse_in("tous les jours à midi, jusqu'au 30 juin 2021", Locale::Fr),
+            english("every day at noon, until june 30, 2021")
+        );
+        assert_eq!(
+            parse_in("une semaine sur deux le mercredi à 8h", Locale::Fr),
+            english("every other wednesday at 8am")
+        );
+        assert_eq!(
+            parse_in("jeden Montag und Donnerstag um 9 Uhr, 10 mal", Locale::De),
+            english("mondays and thursdays at 9am, 10 times")
+        );
+        assert_eq!(
+            parse_in(
+                "miércoles cada 15 minutos de 8:00 a 18:00, desde el 2021-03-01",
+                Locale::Es
+            ),
+            english("wednesday every 15 minutes from 8am to 6pm, starting 2021-03-01")
+        );
+        for (s, locale) in &[
+            ("un vendredi sur deux à 9h", Locale::Fr),
+            ("jeden zweiten Freitag um 9 Uhr", Locale::De),
+            ("cada dos viernes a las 9", Locale::Es),
+        ] {
+            assert_eq!(
+                parse_in(s, *locale),
+                english("every other friday at 9am"),
+                "{}",
+                s
+            );
+        }
+        assert_eq!(
+            parse_in("cada dos sábados a las 9", Locale::Es),
+            english("every other saturday at 9am")
+        );
+        for (s, locale) in &[
+            ("Montag um 9, bis 30. Juni 2021", Locale::De),
+            ("lunes a las 9, hasta el 30 de junio de 2021", Locale::Es),
+        ] {
+            assert_eq!(
+                parse_in(s, *locale),
+                english("monday at 9am, until june 30, 2021"),
+                "{}",
+                s
+            );
+        }
+        assert_eq!(
+            parse_in("lundi à 9h, à partir du 1er mars 2021", Locale::Fr),
+            english("monday at 9am, starting march 1, 2021")
+        );
+        // names of the language only
+        assert!(matches!(
+            Recurrence::parse_in("Thursday um 9", Locale::De, today()),
+            Err(Error::UnexpectedToken { position: 0, .. })
+        ));
+        assert!(Recurrence::parse_english("lundi à 9h", today()).is_err());
+        // too short to name `domingo`, or an abbreviation
+        assert!(Recurrence::parse_in("dos a las 9", Locale::Es, today()).is_err());
+        assert_eq!(
+            parse_in("Mo und Do um 9 Uhr", Locale::De),
+            english("mondays and thursdays at 9am")
+        );
+    }
+
+    #[test]
+    fn locale_descriptions() {
+        let at = |month, day| {
+            NaiveDate::from_ymd_opt(2021, month, day)
+                .unwrap()
+                .and_time(NaiveTime::MIN)
+        };
+        let recurrence = Recurrence::new((Weekday::Mon, Weekday::Fri), hm(9, 0))
+            .unwrap()
+            .with_start(at(3, 1))
+            .with_until(at(6, 30) + Duration::seconds(86_399))
+            .with_count(10)
+            .unwrap();
+        for locale in &[Locale::En, Locale::Fr, Locale::De, Locale::Es] {
+            let description = recurrence.describe_in(*locale);
+            assert_eq!(
+                Recurrence::parse_in(&description, *locale, today())
+                    .unwrap_or_else(|error| panic!("{}: {}", description, error)),
+                recurrence,
+                "{}",
+                description
+            );
+        }
+    }
+
+    #[test]
+    fn descriptions() {
+        let anchor = NaiveDate::from_ymd_opt(2021, 3, 1).unwrap();
+        let recurrence = Recurrence::every(
+            (Weekday::Mon, Weekday::Wed),
+            hm(8, 0),
+            hm(18, 0),
+            Duration::minutes(15),
+        )
+        .unwrap()
+        .with_interval(3, anchor)
+        .unwrap()
+        .with_start(anchor.and_time(NaiveTime::MIN))
+        .with_count(10)
+        .unwrap();
+        assert_eq!(english(&recurrence.describe()), recurrence);
+    }
+
+    #[test]
+    fn errors() {
+        let error = |s| {
+            Recurrence::parse_english(s, today())
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            error("every wekday at 9am"),
+            "Expected a day of week at position 6, found `wekday`"
+        );
+        assert_eq!(
+            error("mon at 9am on"),
+            "Expected `starting`, `until`, a count or the end at position 11, found `on`"
+        );
+        assert_eq!(
+            error("mon at 13pm"),
+            "Expected an hour from 1 to 12 at position 7, found `13`"
+        );
+        assert_eq!(
+            error("mon at 9:75"),
+            "Expected minutes from 00 to 59 at position 9, found `75`"
+        );
+        assert_eq!(
+            error("mon and"),
+            "Expected a time at position 4, found `and`"
+        );
+        assert_eq!(error("mon at"), "Expected a time at the end (position 6)");
+        assert_eq!(
+            error("tue 9, until 2021-02-30"),
+            "Expected a valid date at position 13, found `2021`"
+        );
+        assert!(matches!(
+            Recurrence::parse_english("", today()),
+            Err(Error::UnexpectedEnd { position: 0, .. })
+        ));
+    }
+}
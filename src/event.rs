@@ -0,0 +1,387 @@
+use crate::{Error, Recurrent};
+use chrono::{DateTime, Duration, TimeZone};
+use std::ops::Range;
+
+/// Windows an [`Event`] merges into one when they overlap, before cutting it.
+const MAX_OVERLAPS: usize = 10_000;
+
+fn nano() -> Duration {
+    Duration::nanoseconds(1)
+}
+
+/// Something lasting a while each time it occurs, e.g. opening hours or maintenance windows.
+///
+/// A window starts at each occurrence and lasts `duration`, possibly across midnight or the end
+/// of the week. Overlapping or touching windows make a single one, cut after 10 000 of them:
+/// the `try_` methods fail instead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Event<R: Recurrent> {
+    recurrent: R,
+    duration: Duration,
+}
+
+/// A change of state of an [`Event`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Edge<T: TimeZone> {
+    /// A window starts.
+    Start(DateTime<T>),
+    /// A window ends.
+    End(DateTime<T>),
+}
+
+impl<T: TimeZone> Edge<T> {
+    pub fn at(&self) -> &DateTime<T> {
+        match self {
+            Edge::Start(at) | Edge::End(at) => at,
+        }
+    }
+}
+
+impl<R: Recurrent> Event<R> {
+    pub fn new(recurrent: R, duration: Duration) -> Result<Self, Error> {
+        if duration <= Duration::zero() {
+            return Err(Error::InvalidDuration);
+        }
+        Ok(Event {
+            recurrent,
+            duration,
+        })
+    }
+
+    pub fn recurrent(&self) -> &R {
+        &self.recurrent
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Start of the window `date` falls in, if any.
+    fn covering<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>> {
+        // the latest window to start ends the latest
+        let start = self.recurrent.prev(&(date.clone() + nano()))?;
+        if start.clone() + self.duration > *date {
+            Some(start)
+        } else {
+            None
+        }
+    }
+
+    /// Start of the window the one starting at `start` belongs to, cut after too many overlaps
+    /// unless `strict`, failing then.
+    fn first_start<T: TimeZone>(
+        &self,
+        start: DateTime<T>,
+        strict: bool,
+    ) -> Result<DateTime<T>, Error> {
+        let mut start = start;
+        for _ in 0..MAX_OVERLAPS {
+            match self.recurrent.prev(&start) {
+                Some(prev) if prev.clone() + self.duration >= start => start = prev,
+                _ => return Ok(start),
+            }
+        }
+        if strict {
+            Err(Error::SearchLimit)
+        } else {
+            Ok(start)
+        }
+    }
+
+    /// End of the window the one starting at `start` belongs to, see
+    /// [`first_start`](Self::first_start).
+    fn last_end<T: TimeZone>(
+        &self,
+        start: DateTime<T>,
+        strict: bool,
+    ) -> Result<DateTime<T>, Error> {
+        let mut start = start;
+        let mut end = start.clone() + self.duration;
+        for _ in 0..MAX_OVERLAPS {
+            match self.recurrent.next(&start) {
+                Some(next) if next <= end => {
+                    end = next.clone() + self.duration;
+                    start = next;
+                }
+                _ => return Ok(end),
+            }
+        }
+        if strict {
+            Err(Error::SearchLimit)
+        } else {
+            Ok(end)
+        }
+    }
+
+    fn window<T: TimeZone>(
+        &self,
+        date: &DateTime<T>,
+        strict: bool,
+    ) -> Result<Option<Range<DateTime<T>>>, Error> {
+        let (first, start) = match self.covering(date) {
+            Some(start) => (self.first_start(start.clone(), strict)?, start),
+            None => match self.recurrent.next(date) {
+                Some(start) => (start.clone(), start),
+                None => return Ok(None),
+            },
+        };
+        Ok(Some(first..self.last_end(start, strict)?))
+    }
+
+    fn next_edge_with<T: TimeZone>(
+        &self,
+        date: &DateTime<T>,
+        strict: bool,
+    ) -> Result<Option<Edge<T>>, Error> {
+        Ok(match self.covering(date) {
+            Some(start) => Some(Edge::End(self.last_end(start, strict)?)),
+            None => self.recurrent.next(date).map(Edge::Start),
+        })
+    }
+
+    fn prev_edge_with<T: TimeZone>(
+        &self,
+        date: &DateTime<T>,
+        strict: bool,
+    ) -> Result<Option<Edge<T>>, Error> {
+        let before = date.clone() - nano();
+        Ok(match self.covering(&before) {
+            Some(start) => Some(Edge::Start(self.first_start(start, strict)?)),
+            None => self
+                .recurrent
+                .prev(date)
+                .map(|start| Edge::End(start + self.duration)),
+        })
+    }
+
+    /// Whether `date` falls in a window, start included and end excluded.
+    pub fn is_active<T: TimeZone>(&self, date: &DateTime<T>) -> bool {
+        self.covering(date).is_some()
+    }
+
+    /// The window `date` falls in, or the next one, cut after 10 000 overlapping windows.
+    pub fn current_or_next_window<T: TimeZone>(
+        &self,
+        date: &DateTime<T>,
+    ) -> Option<Range<DateTime<T>>> {
+        self.window(date, false).ok().flatten()
+    }
+
+    /// The window `date` falls in, or the next one, failing with [`Error::SearchLimit`] rather
+    /// than cutting it.
+    pub fn try_current_or_next_window<T: TimeZone>(
+        &self,
+        date: &DateTime<T>,
+    ) -> Result<Option<Range<DateTime<T>>>, Error> {
+        self.window(date, true)
+    }
+
+    /// First start or end strictly after `date`, see
+    /// [`current_or_next_window`](Self::current_or_next_window).
+    pub fn next_edge<T: TimeZone>(&self, date: &DateTime<T>) -> Option<Edge<T>> {
+        self.next_edge_with(date, false).ok().flatten()
+    }
+
+    /// First start or end strictly after `date`, failing with [`Error::SearchLimit`] rather
+    /// than cutting the window.
+    pub fn try_next_edge<T: TimeZone>(&self, date: &DateTime<T>) -> Result<Option<Edge<T>>, Error> {
+        self.next_edge_with(date, true)
+    }
+
+    /// Last start or end strictly before `date`, see [`next_edge`](Self::next_edge).
+    pub fn prev_edge<T: TimeZone>(&self, date: &DateTime<T>) -> Option<Edge<T>> {
+        self.prev_edge_with(date, false).ok().flatten()
+    }
+
+    /// Last start or end strictly before `date`, see [`try_next_edge`](Self::try_next_edge).
+    pub fn try_prev_edge<T: TimeZone>(&self, date: &DateTime<T>) -> Result<Option<Edge<T>>, Error> {
+        self.prev_edge_with(date, true)
+    }
+
+    /// Lazily iterates over the starts and ends strictly after `date`, in chronological order.
+    pub fn edges_after<T: TimeZone>(&self, date: &DateTime<T>) -> Edges<'_, R, T> {
+        Edges {
+            event: self,
+            front: Some(date.clone()),
+            back: None,
+        }
+    }
+
+    /// Lazily iterates over the starts and ends in `[start, end)`.
+    pub fn edges_between<T: TimeZone>(
+        &self,
+        start: &DateTime<T>,
+        end: &DateTime<T>,
+    ) -> Edges<'_, R, T> {
+        Edges {
+            event: self,
+            front: Some(start.clone() - nano()),
+            back: Some(end.clone()),
+        }
+    }
+}
+
+/// Lazy iterator over the [`Edge`]s of an [`Event`], see [`Occurrences`](crate::Occurrences).
+#[derive(Clone, Debug)]
+pub struct Edges<'a, R: Recurrent, T: TimeZone> {
+    event: &'a Event<R>,
+    front: Option<DateTime<T>>,
+    back: Option<DateTime<T>>,
+}
+
+impl<'a, R: Recurrent, T: TimeZone> Iterator for Edges<'a, R, T> {
+    type Item = Edge<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.event.next_edge(self.front.as_ref()?)?;
+        if matches!(&self.back, Some(back) if next.at() >= back) {
+            return None;
+        }
+        self.front = Some(next.at().clone());
+        Some(next)
+    }
+}
+
+impl<'a, R: Recurrent, T: TimeZone> DoubleEndedIterator for Edges<'a, R, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let prev = self.event.prev_edge(self.back.as_ref()?)?;
+        if matches!(&self.front, Some(front) if prev.at() <= front) {
+            return None;
+        }
+        self.back = Some(prev.at().clone());
+        Some(prev)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Edge, Error, Event, Recurrence};
+    use chrono::{DateTime, Duration, NaiveTime, Utc, Weekday};
+
+    fn parse(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn across_midnight() {
+        // friday and sunday nights, 22:00 to 02:00
+        let nights = Event::new(
+            Recurrence::new(
+                (Weekday::Fri, Weekday::Sun),
+                NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+            )
+            .unwrap(),
+            Duration::hours(4),
+        )
+        .unwrap();
+        assert!(nights.is_active(&parse("2020-09-04T22:00:00Z")));
+        assert!(nights.is_active(&parse("2020-09-05T01:59:59Z")));
+        assert!(!nights.is_active(&parse("2020-09-05T02:00:00Z")));
+        // across the end of the week
+        assert!(nights.is_active(&parse("2020-09-07T01:00:00Z")));
+        assert_eq!(
+            nights.current_or_next_window(&parse("2020-09-07T01:00:00Z")),
+            Some(parse("2020-09-06T22:00:00Z")..parse("2020-09-07T02:00:00Z"))
+        );
+        assert_eq!(
+            nights.current_or_next_window(&parse("2020-09-07T02:00:00Z")),
+            Some(parse("2020-09-11T22:00:00Z")..parse("2020-09-12T02:00:00Z"))
+        );
+        assert_eq!(
+            nights
+                .edges_after(&parse("2020-09-05T00:00:00Z"))
+                .take(3)
+                .collect::<Vec<_>>(),
+            vec![
+                Edge::End(parse("2020-09-05T02:00:00Z")),
+                Edge::Start(parse("2020-09-06T22:00:00Z")),
+                Edge::End(parse("2020-09-07T02:00:00Z")),
+            ]
+        );
+        let edges = nights.edges_between(
+            &parse("2020-09-04T22:00:00Z"),
+            &parse("2020-09-07T02:00:00Z"),
+        );
+        assert_eq!(
+            edges.rev().collect::<Vec<_>>(),
+            vec![
+                Edge::Start(parse("2020-09-06T22:00:00Z")),
+                Edge::End(parse("2020-09-05T02:00:00Z")),
+                Edge::Start(parse("2020-09-04T22:00:00Z")),
+            ]
+        );
+    }
+
+    #[test]
+    fn overlapping() {
+        // every 15 minutes for half an hour, from 08:00 to 09:00
+        let event = Event::new(
+            Recurrence::every(
+                Weekday::Mon,
+                NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+                NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                Duration::minutes(15),
+            )
+            .unwrap(),
+            Duration::minutes(30),
+        )
+        .unwrap();
+        let window = parse("2020-09-07T08:00:00Z")..parse("2020-09-07T09:30:00Z");
+        assert_eq!(
+            event.current_or_next_window(&parse("2020-09-07T08:40:00Z")),
+            Some(window.clone())
+        );
+        assert_eq!(
+            event
+                .edges_after(&parse("2020-09-07T00:00:00Z"))
+                .take(2)
+                .collect::<Vec<_>>(),
+            vec![Edge::Start(window.start), Edge::End(window.end)]
+        );
+        assert!(matches!(
+            event.try_current_or_next_window(&parse("2020-09-07T08:40:00Z")),
+            Ok(Some(found)) if found == window
+        ));
+        assert!(matches!(
+            Event::new(event.recurrent().clone(), Duration::zero()),
+            Err(Error::InvalidDuration)
+        ));
+
+        // always on, cut after 10 000 windows
+        let days = [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ];
+        let always = Event::new(
+            Recurrence::every(
+                &days[..],
+                NaiveTime::MIN,
+                NaiveTime::from_hms_opt(23, 59, 0).unwrap(),
+                Duration::minutes(1),
+            )
+            .unwrap(),
+            Duration::minutes(2),
+        )
+        .unwrap();
+        let date = parse("2020-09-07T08:00:30Z");
+        assert_eq!(
+            always.next_edge(&date),
+            Some(Edge::End(
+                parse("2020-09-07T08:00:00Z") + Duration::minutes(10_002)
+            ))
+        );
+        assert!(matches!(
+            always.try_next_edge(&date),
+            Err(Error::SearchLimit)
+        ));
+        assert!(matches!(
+            always.try_prev_edge(&date),
+            Err(Error::SearchLimit)
+        ));
+    }
+}
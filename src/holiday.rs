@@ -0,0 +1,466 @@
+use crate::{
+    dst, DayOfYear, Error, Fold, Gap, MissingDay, OrderedWeekday, Recurrent, RecurrentDay, Weekdays,
+};
+use chrono::{
+    DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Weekday,
+};
+use std::collections::BTreeSet;
+
+/// Days looked through for a business day before giving up.
+const MAX_DAYS: i64 = 31;
+
+/// Days an [`Adjusted`] recurrence goes through looking for an occurrence before giving up, so
+/// that it ends when all of them are skipped: the 400 years the Gregorian calendar repeats after.
+const MAX_SEARCH_DAYS: i64 = 146_097;
+
+/// Tells business days from weekends and holidays.
+pub trait HolidayCalendar {
+    fn is_holiday(&self, date: NaiveDate) -> bool;
+
+    /// Whether `date` falls on a weekend, saturday or sunday by default.
+    fn is_weekend(&self, date: NaiveDate) -> bool {
+        matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+    }
+
+    fn is_business_day(&self, date: NaiveDate) -> bool {
+        !self.is_weekend(date) && !self.is_holiday(date)
+    }
+}
+
+impl<C: HolidayCalendar> HolidayCalendar for &C {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        (*self).is_holiday(date)
+    }
+
+    fn is_weekend(&self, date: NaiveDate) -> bool {
+        (*self).is_weekend(date)
+    }
+}
+
+/// First business day from `date` on, walking `step` days at a time.
+pub(crate) fn business_day<C: HolidayCalendar>(
+    calendar: &C,
+    date: NaiveDate,
+    step: i64,
+) -> Option<NaiveDate> {
+    (0..MAX_DAYS)
+        .map(|days| date + Duration::days(days * step))
+        .find(|date| calendar.is_business_day(*date))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Holiday {
+    Yearly(DayOfYear),
+    /// Days after easter sunday.
+    Easter(i64),
+    Date(NaiveDate),
+}
+
+/// Easter sunday of `year`, in the gregorian calendar.
+fn easter(year: i32) -> NaiveDate {
+    // anonymous gregorian algorithm
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32).unwrap()
+}
+
+/// An in-memory [`HolidayCalendar`], built from yearly and one-off holidays.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Holidays {
+    pub(crate) weekend: BTreeSet<OrderedWeekday>,
+    pub(crate) holidays: Vec<Holiday>,
+}
+
+impl Default for Holidays {
+    fn default() -> Self {
+        Holidays {
+            weekend: [Weekday::Sat, Weekday::Sun]
+                .iter()
+                .map(|day| (*day).into())
+                .collect(),
+            holidays: vec![],
+        }
+    }
+}
+
+impl Holidays {
+    /// No holidays, with saturday and sunday off.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the days of the weekend.
+    pub fn with_weekend<D: Weekdays>(mut self, days: D) -> Self {
+        self.weekend = days.week_days().into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// The given date of every year, e.g. December 25th. February 29th is a holiday on leap years
+    /// only.
+    pub fn with_fixed(mut self, month: u32, day: u32) -> Result<Self, Error> {
+        let day = DayOfYear::date(month, day, MissingDay::Skip)?;
+        self.holidays.push(Holiday::Yearly(day));
+        Ok(self)
+    }
+
+    /// The nth weekday of the given month of every year, e.g. the fourth Thursday of November.
+    pub fn with_nth_weekday(
+        mut self,
+        month: u32,
+        nth: i8,
+        weekday: Weekday,
+    ) -> Result<Self, Error> {
+        let day = DayOfYear::nth_weekday(month, nth, weekday)?;
+        self.holidays.push(Holiday::Yearly(day));
+        Ok(self)
+    }
+
+    /// `days` after easter sunday every year, e.g. -2 for Good Friday or 1 for Easter Monday.
+    pub fn with_easter(mut self, days: i64) -> Self {
+        self.holidays.push(Holiday::Easter(days));
+        self
+    }
+
+    /// A one-off holiday.
+    pub fn with_date(mut self, date: NaiveDate) -> Self {
+        self.holidays.push(Holiday::Date(date));
+        self
+    }
+}
+
+impl HolidayCalendar for Holidays {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays.iter().any(|holiday| match holiday {
+            Holiday::Yearly(day) => day.falls_on(date),
+            Holiday::Easter(days) => {
+                // may be around new year for large offsets
+                (date.year() - 1..=date.year() + 1)
+                    .any(|year| easter(year) + Duration::days(*days) == date)
+            }
+            Holiday::Date(holiday) => *holiday == date,
+        })
+    }
+
+    fn is_weekend(&self, date: NaiveDate) -> bool {
+        self.weekend.contains(&date.weekday().into())
+    }
+}
+
+/// Where an occurrence falling on a weekend or a holiday goes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Adjustment {
+    /// Nowhere, it doesn't occur.
+    Skip,
+    /// To the previous business day.
+    Preceding,
+    /// To the next business day.
+    Following,
+    /// To the next business day, unless it is in the next month: to the previous one then.
+    ModifiedFollowing,
+}
+
+/// Occurrences of a [`Recurrent`] moved off weekends and holidays, see [`Recurrent::adjusted`].
+///
+/// Occurrences keep their time of day, resolved around daylight saving time changes as the
+/// wrapped recurrent's are. Ends once 400 years of occurrences in a row have been skipped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Adjusted<R, C> {
+    pub(crate) recurrent: R,
+    pub(crate) calendar: C,
+    pub(crate) adjustment: Adjustment,
+}
+
+impl<R: Recurrent, C: HolidayCalendar> Adjusted<R, C> {
+    pub(crate) fn new(recurrent: R, calendar: C, adjustment: Adjustment) -> Self {
+        Adjusted {
+            recurrent,
+            calendar,
+            adjustment,
+        }
+    }
+
+    /// Date occurrences on `date` are moved to, if anywhere.
+    fn adjust(&self, date: NaiveDate) -> Option<NaiveDate> {
+        if self.calendar.is_business_day(date) {
+            return Some(date);
+        }
+        match self.adjustment {
+            Adjustment::Skip => None,
+            Adjustment::Preceding => business_day(&self.calendar, date, -1),
+            Adjustment::Following => business_day(&self.calendar, date, 1),
+            Adjustment::ModifiedFollowing => business_day(&self.calendar, date, 1)
+                .filter(|following| following.month() == date.month())
+                .or_else(|| business_day(&self.calendar, date, -1)),
+        }
+    }
+
+    /// `occurrence` moved to `date`, at the same wall time.
+    fn moved<T: TimeZone>(&self, occurrence: &DateTime<T>, date: NaiveDate) -> Option<DateTime<T>> {
+        let local = date.and_time(occurrence.naive_local().time());
+        let (gap, fold) = self.recurrent.dst_policy();
+        dst::resolve(&occurrence.timezone(), local, gap, fold)
+    }
+}
+
+/// The instant `local` happens at in `tz`, or `occurrence` if that is not on the `later` side of
+/// it: searches go on from there.
+fn resume<T: TimeZone>(
+    tz: &T,
+    local: NaiveDateTime,
+    occurrence: DateTime<T>,
+    later: bool,
+) -> DateTime<T> {
+    match dst::resolve(tz, local, Gap::Shift, Fold::Earliest) {
+        Some(from) if (from > occurrence) == later && from != occurrence => from,
+        _ => occurrence,
+    }
+}
+
+impl<R: Recurrent, C: HolidayCalendar> Recurrent for Adjusted<R, C> {
+    /// Goes through the occurrences of the wrapped recurrent day by day, the ones of a day all
+    /// moving to the same date.
+    fn next<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>> {
+        let tz = date.timezone();
+        let local = date.naive_local();
+        let reach = Duration::days(MAX_DAYS);
+        let limit = local.date() + Duration::days(MAX_SEARCH_DAYS);
+        let mut best: Option<DateTime<T>> = None;
+        // occurrences move by less than MAX_DAYS
+        let mut next = self.recurrent.next(&(date.clone() - reach));
+        while let Some(occurrence) = next {
+            let source = occurrence.naive_local();
+            let day = source.date();
+            if day > limit || matches!(&best, Some(best) if day - reach > best.naive_local().date())
+            {
+                break;
+            }
+            // the last instant of the day by default, skipping its other occurrences
+            let mut from =
+                day.and_time(NaiveTime::MIN) + Duration::days(1) - Duration::nanoseconds(1);
+            if let Some(adjusted) = self
+                .adjust(day)
+                .filter(|adjusted| *adjusted >= local.date())
+            {
+                match self.moved(&occurrence, adjusted) {
+                    Some(moved) if moved > *date => {
+                        if best.as_ref().is_none_or(|best| moved < *best) {
+                            best = Some(moved);
+                        }
+                    }
+                    // later times of the day may move after `date`
+                    _ if adjusted == local.date() => from = source.max(day.and_time(local.time())),
+                    _ => from = source,
+                }
+            }
+            next = self.recurrent.next(&resume(&tz, from, occurrence, true));
+        }
+        best
+    }
+
+    fn prev<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>> {
+        let tz = date.timezone();
+        let local = date.naive_local();
+        let reach = Duration::days(MAX_DAYS);
+        let limit = local.date() - Duration::days(MAX_SEARCH_DAYS);
+        let mut best: Option<DateTime<T>> = None;
+        let mut prev = self.recurrent.prev(&(date.clone() + reach));
+        while let Some(occurrence) = prev {
+            let source = occurrence.naive_local();
+            let day = source.date();
+            if day < limit || matches!(&best, Some(best) if day + reach < best.naive_local().date())
+            {
+                break;
+            }
+            // midnight by default, skipping the other occurrences of the day
+            let mut from = day.and_time(NaiveTime::MIN);
+            if let Some(adjusted) = self
+                .adjust(day)
+                .filter(|adjusted| *adjusted <= local.date())
+            {
+                match self.moved(&occurrence, adjusted) {
+                    Some(moved) if moved < *date => {
+                        if best.as_ref().is_none_or(|best| moved > *best) {
+                            best = Some(moved);
+                        }
+                    }
+                    // earlier times of the day may move before `date`
+                    _ if adjusted == local.date() => from = source.min(day.and_time(local.time())),
+                    _ => from = source,
+                }
+            }
+            prev = self.recurrent.prev(&resume(&tz, from, occurrence, false));
+        }
+        best
+    }
+
+    fn dst_policy(&self) -> (Gap, Fold) {
+        self.recurrent.dst_policy()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Adjustment, Cron, Gap, HolidayCalendar, Holidays, MissingDay, Recurrence, Recurrent,
+    };
+    use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+    use chrono_tz::Europe::Paris;
+
+    fn parse(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn holidays() -> Holidays {
+        Holidays::new()
+            .with_fixed(12, 25)
+            .unwrap()
+            .with_nth_weekday(11, 4, Weekday::Thu)
+            .unwrap()
+            .with_easter(-2)
+            .with_easter(1)
+            .with_date(date(2021, 7, 30))
+    }
+
+    #[test]
+    fn calendar() {
+        let holidays = holidays();
+        assert!(holidays.is_holiday(date(2021, 12, 25)));
+        assert!(holidays.is_holiday(date(2021, 11, 25)));
+        // good friday and easter monday
+        assert!(holidays.is_holiday(date(2021, 4, 2)));
+        assert!(holidays.is_holiday(date(2021, 4, 5)));
+        assert!(holidays.is_holiday(date(2024, 3, 29)));
+        assert!(!holidays.is_holiday(date(2021, 4, 4)));
+        assert!(!holidays.is_business_day(date(2021, 4, 4)));
+        assert!(holidays.is_business_day(date(2021, 4, 6)));
+        let friday_off = Holidays::new().with_weekend((Weekday::Fri, Weekday::Sat));
+        assert!(friday_off.is_business_day(date(2021, 4, 4)));
+    }
+
+    #[test]
+    fn payroll() {
+        // every friday, or the previous business day if friday is a holiday
+        let payroll = Recurrence::new(Weekday::Fri, NaiveTime::from_hms_opt(12, 0, 0).unwrap())
+            .unwrap()
+            .adjusted(holidays(), Adjustment::Preceding);
+        assert_eq!(
+            payroll
+                .occurrences_after(&parse("2021-03-26T12:00:00Z"))
+                .take(2)
+                .collect::<Vec<_>>(),
+            vec![parse("2021-04-01T12:00:00Z"), parse("2021-04-09T12:00:00Z")]
+        );
+        assert_eq!(
+            payroll.prev(&parse("2021-04-09T12:00:00Z")),
+            Some(parse("2021-04-01T12:00:00Z"))
+        );
+        // moved before the date it is asked from
+        assert_eq!(
+            payroll.next(&parse("2021-04-01T13:00:00Z")),
+            Some(parse("2021-04-09T12:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn adjustments() {
+        let noon = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+        // july 31st 2021 is a saturday, july 30th a holiday
+        let month_end = Recurrence::monthly(&[-1], noon, MissingDay::Skip).unwrap();
+        let adjusted = |adjustment| {
+            (&month_end)
+                .adjusted(holidays(), adjustment)
+                .next(&parse("2021-07-01T00:00:00Z"))
+        };
+        assert_eq!(
+            adjusted(Adjustment::Skip),
+            Some(parse("2021-08-31T12:00:00Z"))
+        );
+        assert_eq!(
+            adjusted(Adjustment::Preceding),
+            Some(parse("2021-07-29T12:00:00Z"))
+        );
+        assert_eq!(
+            adjusted(Adjustment::Following),
+            Some(parse("2021-08-02T12:00:00Z"))
+        );
+        assert_eq!(
+            adjusted(Adjustment::ModifiedFollowing),
+            Some(parse("2021-07-29T12:00:00Z"))
+        );
+        // only ever on weekends
+        let weekend = Recurrence::new(Weekday::Sun, noon)
+            .unwrap()
+            .adjusted(Holidays::new(), Adjustment::Skip);
+        assert_eq!(weekend.next(&parse("2021-07-01T00:00:00Z")), None);
+    }
+
+    #[test]
+    fn dense() {
+        // every minute, weekend ones moved to monday
+        let minutes = "* * * * *"
+            .parse::<Cron>()
+            .unwrap()
+            .adjusted(Holidays::new(), Adjustment::Following);
+        assert_eq!(
+            minutes.next(&parse("2021-07-07T12:00:00Z")),
+            Some(parse("2021-07-07T12:01:00Z"))
+        );
+        assert_eq!(
+            minutes.next(&parse("2021-07-10T12:00:00Z")),
+            Some(parse("2021-07-12T00:00:00Z"))
+        );
+        assert_eq!(
+            minutes.prev(&parse("2021-07-12T00:00:00Z")),
+            Some(parse("2021-07-09T23:59:00Z"))
+        );
+    }
+
+    #[test]
+    fn dst_policy() {
+        // saturdays moved to sundays, 02:30 doesn't exist on march 28th 2021 in Paris
+        let nights = Recurrence::new(Weekday::Sat, NaiveTime::from_hms_opt(2, 30, 0).unwrap())
+            .unwrap()
+            .with_gap(Gap::Skip)
+            .adjusted(
+                Holidays::new().with_weekend((Weekday::Fri, Weekday::Sat)),
+                Adjustment::Following,
+            );
+        let date = Paris.with_ymd_and_hms(2021, 3, 27, 12, 0, 0).unwrap();
+        assert_eq!(
+            nights.next(&date),
+            Some(Paris.with_ymd_and_hms(2021, 4, 4, 2, 30, 0).unwrap())
+        );
+        // combinations resolve as their first side does
+        let night = |weekday| {
+            Recurrence::new(weekday, NaiveTime::from_hms_opt(2, 30, 0).unwrap())
+                .unwrap()
+                .with_gap(Gap::Skip)
+        };
+        let nights = night(Weekday::Fri).union(night(Weekday::Sat)).adjusted(
+            Holidays::new().with_weekend((Weekday::Fri, Weekday::Sat)),
+            Adjustment::Following,
+        );
+        assert_eq!(
+            nights.next(&date),
+            Some(Paris.with_ymd_and_hms(2021, 4, 4, 2, 30, 0).unwrap())
+        );
+    }
+}
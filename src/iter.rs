@@ -0,0 +1,173 @@
+use crate::Recurrent;
+use chrono::{DateTime, TimeZone};
+
+/// Lazy iterator over the occurrences of anything [`Recurrent`].
+///
+/// The iterator walks a window delimited by two exclusive cursors: `next` yields the occurrence
+/// following the front cursor and `next_back` the one preceding the back cursor. An unbounded
+/// side never yields anything, so walk an unbounded window from its bounded end.
+#[derive(Clone, Debug)]
+pub struct Occurrences<'a, R: Recurrent, T: TimeZone> {
+    recurrent: &'a R,
+    front: Option<DateTime<T>>,
+    back: Option<DateTime<T>>,
+}
+
+impl<'a, R: Recurrent, T: TimeZone> Occurrences<'a, R, T> {
+    pub(crate) fn new(
+        recurrent: &'a R,
+        front: Option<DateTime<T>>,
+        back: Option<DateTime<T>>,
+    ) -> Self {
+        Occurrences {
+            recurrent,
+            front,
+            back,
+        }
+    }
+}
+
+impl<'a, R: Recurrent, T: TimeZone> Iterator for Occurrences<'a, R, T> {
+    type Item = DateTime<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.recurrent.next(self.front.as_ref()?)?;
+        if matches!(&self.back, Some(back) if next >= *back) {
+            return None;
+        }
+        self.front = Some(next.clone());
+        Some(next)
+    }
+}
+
+impl<'a, R: Recurrent, T: TimeZone> DoubleEndedIterator for Occurrences<'a, R, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let prev = self.recurrent.prev(self.back.as_ref()?)?;
+        if matches!(&self.front, Some(front) if prev <= *front) {
+            return None;
+        }
+        self.back = Some(prev.clone());
+        Some(prev)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Recurrence, Recurrent};
+    use chrono::{DateTime, NaiveTime, Utc, Weekday};
+
+    fn parse(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn after() {
+        let recurrence = Recurrence::new(
+            (Weekday::Mon, Weekday::Fri),
+            NaiveTime::from_hms_opt(14, 0, 0).unwrap(),
+        )
+        .unwrap();
+        let occurrences: Vec<_> = recurrence
+            .occurrences_after(&parse("2020-08-31T14:00:00Z"))
+            .take(4)
+            .collect();
+        assert_eq!(
+            occurrences,
+            vec![
+                parse("2020-09-04T14:00:00Z"),
+                parse("2020-09-07T14:00:00Z"),
+                parse("2020-09-11T14:00:00Z"),
+                parse("2020-09-14T14:00:00Z"),
+            ]
+        );
+    }
+
+    #[test]
+    fn before() {
+        let recurrence =
+            Recurrence::new(Weekday::Sun, NaiveTime::from_hms_opt(15, 0, 0).unwrap()).unwrap();
+        let occurrences: Vec<_> = recurrence
+            .occurrences_before(&parse("2020-08-30T15:00:00Z"))
+            .take_while(|o| *o > parse("2020-08-01T00:00:00Z"))
+            .collect();
+        assert_eq!(
+            occurrences,
+            vec![
+                parse("2020-08-23T15:00:00Z"),
+                parse("2020-08-16T15:00:00Z"),
+                parse("2020-08-09T15:00:00Z"),
+                parse("2020-08-02T15:00:00Z"),
+            ]
+        );
+        // walking towards the unbounded side yields nothing
+        assert_eq!(
+            recurrence
+                .occurrences_before(&parse("2020-08-30T15:00:00Z"))
+                .next_back(),
+            None
+        );
+    }
+
+    #[test]
+    fn between() {
+        let recurrence = Recurrence::new(
+            (Weekday::Mon, Weekday::Wed),
+            NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+        )
+        .unwrap();
+        let start = parse("2020-08-31T09:00:00Z");
+        let end = parse("2020-09-09T09:00:00Z");
+        let expected = vec![
+            parse("2020-08-31T09:00:00Z"),
+            parse("2020-09-02T09:00:00Z"),
+            parse("2020-09-07T09:00:00Z"),
+        ];
+        assert_eq!(
+            recurrence.between(&start, &end, false).collect::<Vec<_>>(),
+            expected
+        );
+        let mut inclusive: Vec<_> = recurrence.between(&start, &end, true).rev().collect();
+        inclusive.reverse();
+        assert_eq!(inclusive[..3], expected[..]);
+        assert_eq!(inclusive[3], end);
+        // both ends meet without duplicating an occurrence
+        let mut occurrences = recurrence.between(&start, &end, true);
+        assert_eq!(occurrences.next(), Some(expected[0]));
+        assert_eq!(occurrences.next_back(), Some(end));
+        assert_eq!(occurrences.next_back(), Some(expected[2]));
+        assert_eq!(occurrences.next(), Some(expected[1]));
+        assert_eq!(occurrences.next(), None);
+        assert_eq!(occurrences.next_back(), None);
+    }
+
+    #[test]
+    fn count_between() {
+        let recurrence = Recurrence::new(
+            (Weekday::Tue, Weekday::Sat, Weekday::Sun),
+            NaiveTime::from_hms_opt(18, 30, 0).unwrap(),
+        )
+        .unwrap();
+        let start = parse("2020-08-30T18:30:00Z");
+        for end in &[
+            "2020-08-30T18:30:00Z",
+            "2020-09-01T18:29:59Z",
+            "2020-09-01T18:30:00Z",
+            "2020-10-11T00:00:00Z",
+            "2021-03-07T18:30:00Z",
+        ] {
+            let end = parse(end);
+            for inclusive in &[false, true] {
+                assert_eq!(
+                    recurrence.count_between(&start, &end, *inclusive),
+                    recurrence.between(&start, &end, *inclusive).count()
+                );
+            }
+        }
+        assert_eq!(recurrence.count_between(&start, &start, true), 1);
+        assert_eq!(recurrence.count_between(&start, &start, false), 0);
+        assert_eq!(
+            recurrence.count_between(&parse("2020-09-01T00:00:00Z"), &start, true),
+            0
+        );
+    }
+}
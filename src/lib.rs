@@ -2,13 +2,54 @@
 
 // transient event => transient state
 
-use chrono::{DateTime, Datelike, Duration, NaiveTime, TimeZone, Timelike, Weekday};
+use chrono::{
+    DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Weekday,
+};
+use std::cmp::Ordering;
 use std::collections::BTreeSet;
+use std::iter::Rev;
+use times::DayTimes;
 
+mod business;
+mod combine;
 mod conv;
+mod cron;
+mod describe;
+mod dst;
+mod english;
+mod event;
+mod holiday;
+mod iter;
+mod locale;
+mod monthly;
+mod rrule;
+mod schedule;
+#[cfg(feature = "serde")]
+mod serialize;
+mod state;
+mod times;
+mod week;
+mod yearly;
+mod zoned;
+
+pub use business::BusinessDays;
+pub use combine::{Difference, Intersection, Union};
+pub use cron::Cron;
+pub use dst::{Fold, Gap};
+pub use event::{Edge, Edges, Event};
+pub use holiday::{Adjusted, Adjustment, HolidayCalendar, Holidays};
+pub use iter::Occurrences;
+pub use locale::Locale;
+pub use monthly::{DayOfMonth, MissingDay, NthWeekday};
+pub use schedule::{Occurrence, Schedule};
+pub use state::{StateMachine, Transition, Transitions};
+pub use week::WeekStart;
+pub use yearly::DayOfYear;
+pub use zoned::Zoned;
 
 /// Internal Weekday representation ordered by day in week.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OrderedWeekday {
     /// Monday.
     Mon = 0,
@@ -32,7 +73,7 @@ impl DurationTo for OrderedWeekday {
         let next = next as isize;
         let mut duration = next - current;
         if duration < 1 {
-            duration = duration + 7;
+            duration += 7;
         }
         Duration::days(duration as i64)
     }
@@ -42,7 +83,7 @@ impl DurationTo for OrderedWeekday {
         let prev = prev as isize;
         let mut duration = prev - current;
         if duration >= 0 {
-            duration = duration - 7;
+            duration -= 7;
         }
         Duration::days(duration as i64)
     }
@@ -54,92 +95,663 @@ pub trait DurationTo {
     fn duration_from(&self, prev: Self) -> Duration;
 }
 
-fn apply_time<T: TimeZone>(date_time: &DateTime<T>, time: &NaiveTime) -> DateTime<T> {
-    date_time
-        .with_hour(time.hour())
-        .unwrap()
-        .with_minute(time.minute())
-        .unwrap()
-        .with_second(time.second())
-        .unwrap()
-        .with_nanosecond(0)
-        .unwrap()
+/// Number of days in the given month.
+pub(crate) fn days_in_month(year: i32, month: u32) -> u32 {
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Exclusive cursors delimiting the `[start, end)` or `[start, end]` window.
+fn window<T: TimeZone>(
+    start: &DateTime<T>,
+    end: &DateTime<T>,
+    inclusive: bool,
+) -> (DateTime<T>, DateTime<T>) {
+    let nano = Duration::nanoseconds(1);
+    let upper = if inclusive {
+        end.clone() + nano
+    } else {
+        end.clone()
+    };
+    (start.clone() - nano, upper)
 }
 
 pub trait Weekdays {
     fn week_days(&self) -> Vec<Weekday>;
 }
 
-#[derive(thiserror::Error, Debug)]
+pub trait Times {
+    fn times(&self) -> Vec<NaiveTime>;
+}
+
+#[derive(thiserror::Error, Clone, Debug, PartialEq, Eq)]
 pub enum Error {
-    #[error("At least one WeekDay must be provided")]
+    #[error("At least one day must be provided")]
     Empty,
+    #[error("At least one time of day must be provided")]
+    NoTime,
+    #[error("Invalid day of month: {0}")]
+    InvalidDayOfMonth(i8),
+    #[error("Invalid weekday ordinal: {0}")]
+    InvalidNth(i8),
+    #[error("Invalid month: {0}")]
+    InvalidMonth(u32),
+    #[error("Interval must be at least 1")]
+    InvalidInterval,
+    #[error("Time window must not end before it starts")]
+    InvalidWindow,
+    #[error("Step must be a whole number of seconds, shorter than a day")]
+    InvalidStep,
+    #[error("Invalid RRULE: {0}")]
+    InvalidRRule(String),
+    #[error("Unsupported RRULE part: {0}")]
+    UnsupportedRRule(String),
+    #[error("Invalid cron expression: {0}")]
+    InvalidCron(String),
+    #[error("Unsupported cron expression: {0}")]
+    UnsupportedCron(String),
+    #[error("Unknown time zone: {0}")]
+    UnknownTimeZone(String),
+    #[error("Count must be at least 1")]
+    InvalidCount,
+    #[error("Counting occurrences needs a start")]
+    NoStart,
+    #[error("No occurrence at {0}")]
+    NoOccurrence(NaiveDateTime),
+    #[error("Duration must be positive")]
+    InvalidDuration,
+    #[error("Gave up looking for an occurrence past the search limit")]
+    SearchLimit,
+    #[error("Expected {expected} at position {position}, found `{found}`")]
+    UnexpectedToken {
+        position: usize,
+        expected: String,
+        found: String,
+    },
+    #[error("Expected {expected} at the end (position {position})")]
+    UnexpectedEnd { position: usize, expected: String },
+}
+
+/// Something occurring over and over: a [`Recurrence`], a [`Schedule`], a combination of
+/// them...
+pub trait Recurrent {
+    /// First occurrence strictly after `date`, `None` once it has ended.
+    fn next<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>>;
+
+    /// Last occurrence strictly before `date`, `None` before it has started.
+    fn prev<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>>;
+
+    /// How wall times that don't exist or happen twice resolve around daylight saving time
+    /// changes, the defaults unless set otherwise.
+    fn dst_policy(&self) -> (Gap, Fold) {
+        (Gap::default(), Fold::default())
+    }
+
+    /// Lazily iterates over the occurrences strictly after `date`, in chronological order.
+    fn occurrences_after<T: TimeZone>(&self, date: &DateTime<T>) -> Occurrences<'_, Self, T>
+    where
+        Self: Sized,
+    {
+        Occurrences::new(self, Some(date.clone()), None)
+    }
+
+    /// Lazily iterates over the occurrences strictly before `date`, walking back in time.
+    fn occurrences_before<T: TimeZone>(&self, date: &DateTime<T>) -> Rev<Occurrences<'_, Self, T>>
+    where
+        Self: Sized,
+    {
+        Occurrences::new(self, None, Some(date.clone())).rev()
+    }
+
+    /// Lazily iterates over the occurrences in `[start, end)`, or `[start, end]` when
+    /// `inclusive` is set.
+    fn between<T: TimeZone>(
+        &self,
+        start: &DateTime<T>,
+        end: &DateTime<T>,
+        inclusive: bool,
+    ) -> Occurrences<'_, Self, T>
+    where
+        Self: Sized,
+    {
+        let (lower, upper) = window(start, end, inclusive);
+        Occurrences::new(self, Some(lower), Some(upper))
+    }
+
+    /// Lazily iterates over the occurrences in the week `date` falls in, from midnight on its
+    /// first day, weeks starting on `week_start`.
+    fn occurrences_in_week<T: TimeZone>(
+        &self,
+        date: &DateTime<T>,
+        week_start: WeekStart,
+    ) -> Occurrences<'_, Self, T>
+    where
+        Self: Sized,
+    {
+        let tz = date.timezone();
+        let first = week_start.first_day(date.naive_local().date());
+        let midnight = |date: NaiveDate| {
+            dst::resolve(
+                &tz,
+                date.and_time(NaiveTime::MIN),
+                Gap::Shift,
+                Fold::Earliest,
+            )
+            .expect("date out of range")
+        };
+        self.between(
+            &midnight(first),
+            &midnight(first + Duration::days(7)),
+            false,
+        )
+    }
+
+    /// Occurs whenever either `self` or `other` does.
+    fn union<R: Recurrent>(self, other: R) -> Union<Self, R>
+    where
+        Self: Sized,
+    {
+        Union::new(self, other)
+    }
+
+    /// Occurs whenever both `self` and `other` do.
+    fn intersection<R: Recurrent>(self, other: R) -> Intersection<Self, R>
+    where
+        Self: Sized,
+    {
+        Intersection::new(self, other)
+    }
+
+    /// Occurs whenever `self` does but `other` doesn't.
+    fn difference<R: Recurrent>(self, other: R) -> Difference<Self, R>
+    where
+        Self: Sized,
+    {
+        Difference::new(self, other)
+    }
+
+    /// Moves the occurrences falling on a weekend or a holiday of `calendar` as `adjustment`
+    /// says.
+    fn adjusted<C: HolidayCalendar>(self, calendar: C, adjustment: Adjustment) -> Adjusted<Self, C>
+    where
+        Self: Sized,
+    {
+        Adjusted::new(self, calendar, adjustment)
+    }
+}
+
+impl<R: Recurrent> Recurrent for &R {
+    fn next<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>> {
+        (*self).next(date)
+    }
+
+    fn prev<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>> {
+        (*self).prev(date)
+    }
+
+    fn dst_policy(&self) -> (Gap, Fold) {
+        (*self).dst_policy()
+    }
+}
+
+/// A day a [`Recurrence`] repeats on, within a week, a month...
+pub trait RecurrentDay: Copy + Ord + std::fmt::Debug {
+    /// Whether this day falls on `date`.
+    fn falls_on(&self, date: NaiveDate) -> bool;
+
+    /// Index of the period (week, month, year...) `date` belongs to, for recurrences repeating
+    /// every n periods.
+    fn period(date: NaiveDate) -> i64;
+
+    /// Like [`period`](Self::period), weeks starting on `week_start`.
+    fn period_with(date: NaiveDate, _week_start: WeekStart) -> i64 {
+        Self::period(date)
+    }
+
+    /// Number of periods after which days fall on the same dates of their period again: the 400
+    /// years the Gregorian calendar repeats after, in periods.
+    const CYCLE: i64;
+
+    /// Date in the period this day belongs to when it falls on `date`: `date` itself, or the day
+    /// before for days rolled over from the previous period.
+    fn origin(&self, date: NaiveDate) -> NaiveDate {
+        date
+    }
+
+    /// First date strictly after `date` one of `days` falls on, `None` past the last date.
+    ///
+    /// `days` must not be empty and must fall on some date eventually.
+    fn next_date(days: &BTreeSet<Self>, date: NaiveDate) -> Option<NaiveDate> {
+        let mut date = date;
+        loop {
+            date = date.succ_opt()?;
+            if days.iter().any(|day| day.falls_on(date)) {
+                return Some(date);
+            }
+        }
+    }
+
+    /// Last date strictly before `date` one of `days` falls on, `None` before the first date.
+    ///
+    /// `days` must not be empty and must fall on some date eventually.
+    fn prev_date(days: &BTreeSet<Self>, date: NaiveDate) -> Option<NaiveDate> {
+        let mut date = date;
+        loop {
+            date = date.pred_opt()?;
+            if days.iter().any(|day| day.falls_on(date)) {
+                return Some(date);
+            }
+        }
+    }
+}
+
+impl RecurrentDay for OrderedWeekday {
+    fn falls_on(&self, date: NaiveDate) -> bool {
+        OrderedWeekday::from(date.weekday()) == *self
+    }
+
+    fn period(date: NaiveDate) -> i64 {
+        WeekStart::MONDAY.week(date)
+    }
+
+    // every week has all weekdays
+    const CYCLE: i64 = 1;
+
+    fn period_with(date: NaiveDate, week_start: WeekStart) -> i64 {
+        week_start.week(date)
+    }
+
+    fn next_date(days: &BTreeSet<Self>, date: NaiveDate) -> Option<NaiveDate> {
+        let current_day: OrderedWeekday = date.weekday().into();
+        let next_week_day = days
+            .iter()
+            .find(|day| *day > &current_day)
+            .unwrap_or(days.iter().find(|_| true).unwrap()); // loop to the first
+        date.checked_add_signed(current_day.duration_to(*next_week_day))
+    }
+
+    fn prev_date(days: &BTreeSet<Self>, date: NaiveDate) -> Option<NaiveDate> {
+        let current_day: OrderedWeekday = date.weekday().into();
+        let prev_week_day = days
+            .iter()
+            .rev()
+            .find(|day| *day < &current_day)
+            .unwrap_or(days.iter().rev().find(|_| true).unwrap()); // loop to the last
+        date.checked_add_signed(current_day.duration_from(*prev_week_day))
+    }
 }
 
-/// Something Recurrent week to week
+/// Something Recurrent week to week, month to month...
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Recurrence<T>
 where
-    T: DurationTo,
+    T: RecurrentDay,
 {
     days: BTreeSet<T>,
-    time: NaiveTime,
+    times: DayTimes,
+    interval: Option<Interval>,
+    gap: Gap,
+    fold: Fold,
+    start: Option<NaiveDateTime>,
+    until: Option<NaiveDateTime>,
+    count: Option<u32>,
+    /// Wall time of the `count`th occurrence, computed once the bounds are set.
+    last: Option<NaiveDateTime>,
+    week_start: WeekStart,
+}
+
+/// The `count`th of the wall times `next_local` walks through from `start` on, when both are set.
+pub(crate) fn nth_local(
+    start: Option<NaiveDateTime>,
+    count: Option<u32>,
+    next_local: impl Fn(NaiveDateTime) -> Option<NaiveDateTime>,
+) -> Option<NaiveDateTime> {
+    let (start, count) = (start?, count?);
+    let mut last = start - Duration::nanoseconds(1);
+    for _ in 0..count {
+        match next_local(last) {
+            Some(next) => last = next,
+            None => break,
+        }
+    }
+    Some(last)
+}
+
+/// The wall time occurrences end at, the earliest of the `last` counted one and `until`.
+pub(crate) fn end(
+    last: Option<NaiveDateTime>,
+    until: Option<NaiveDateTime>,
+) -> Option<NaiveDateTime> {
+    match (last, until) {
+        (Some(last), Some(until)) => Some(last.min(until)),
+        (last, until) => last.or(until),
+    }
+}
+
+/// Repeat every `every` periods, in phase with the period of `anchor`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Interval {
+    every: u32,
+    anchor: NaiveDate,
 }
 
 impl Recurrence<OrderedWeekday> {
-    pub fn new<T: Weekdays>(days: T, time: NaiveTime) -> Result<Self, Error> {
+    pub fn new<D: Weekdays, S: Times>(days: D, times: S) -> Result<Self, Error> {
         let days = days.week_days();
-        if days.len() == 0 {
+        Self::from_days(days.iter().map(|d| (*d).into()).collect(), times)
+    }
+
+    /// Repeats every `step` from `from` up to and including `to` on the given days, e.g. every
+    /// 15 minutes between 08:00 and 18:00 on weekdays.
+    pub fn every<D: Weekdays>(
+        days: D,
+        from: NaiveTime,
+        to: NaiveTime,
+        step: Duration,
+    ) -> Result<Self, Error> {
+        let mut recurrence = Self::new(days, from)?;
+        recurrence.times = DayTimes::every(from, to, step)?;
+        Ok(recurrence)
+    }
+
+    /// Counts the occurrences `between` would yield without walking through them.
+    ///
+    /// Occurrences are counted on the wall clock: ones skipped or repeated by a daylight saving
+    /// time change count once.
+    pub fn count_between<T: TimeZone>(
+        &self,
+        start: &DateTime<T>,
+        end: &DateTime<T>,
+        inclusive: bool,
+    ) -> usize {
+        if end < start {
+            return 0;
+        }
+        let (lower, upper) = window(start, end, inclusive);
+        // upper is exclusive, the last instant it covers is one nanosecond before
+        let mut upper = (upper - Duration::nanoseconds(1)).naive_local();
+        let mut lower = lower.naive_local();
+        if let Some(end) = self.end() {
+            upper = upper.min(end);
+        }
+        if let Some(start) = self.start {
+            lower = lower.max(start - Duration::nanoseconds(1));
+        }
+        let first = self.week_start.first_day(lower.date());
+        let count = self.count_since(first, upper) - self.count_since(first, lower);
+        count.max(0) as usize
+    }
+
+    /// Number of occurrences from midnight on `first`, the first day of a week, up to and
+    /// including `until`.
+    fn count_since(&self, first: NaiveDate, until: NaiveDateTime) -> i64 {
+        let weeks = (until.date() - first).num_days().div_euclid(7);
+        let first_week = self.week_start.week(first);
+        let full_weeks = match self.interval {
+            None => weeks,
+            Some(interval) => {
+                // weeks in phase within [first_week, first_week + weeks)
+                let every = interval.every as i64;
+                let phase = self.week_start.week(interval.anchor);
+                (first_week + weeks - phase - 1).div_euclid(every)
+                    - (first_week - phase - 1).div_euclid(every)
+            }
+        };
+        if !self.in_phase(until.date()) {
+            return full_weeks * (self.days.len() * self.times.len()) as i64;
+        }
+        let position = |day| self.week_start.position(day);
+        let current_day = position(until.weekday().into());
+        let per_day = self.times.len();
+        let this_week: usize = self
+            .days
+            .iter()
+            .map(|day| match position(*day).cmp(&current_day) {
+                Ordering::Less => per_day,
+                Ordering::Equal => self.times.count_until(until.time()),
+                Ordering::Greater => 0,
+            })
+            .sum();
+        full_weeks * (self.days.len() * per_day) as i64 + this_week as i64
+    }
+}
+
+impl<D: RecurrentDay> Recurrence<D> {
+    fn from_days<S: Times>(days: BTreeSet<D>, times: S) -> Result<Self, Error> {
+        let times = times.times();
+        if days.is_empty() {
             Err(Error::Empty)
+        } else if times.is_empty() {
+            Err(Error::NoTime)
         } else {
             Ok(Recurrence {
-                time,
-                days: days.iter().map(|d| (*d).into()).collect(),
+                days,
+                times: DayTimes::List(times.into_iter().collect()),
+                interval: None,
+                gap: Gap::default(),
+                fold: Fold::default(),
+                start: None,
+                until: None,
+                count: None,
+                last: None,
+                week_start: WeekStart::default(),
             })
         }
     }
 
-    pub fn next<T: TimeZone>(&self, date: &DateTime<T>) -> DateTime<T> {
-        let current_day: OrderedWeekday = date.weekday().into();
-
-        let days_to_add = if self.days.contains(&current_day) && date.time() < self.time {
-            // next is current day :)
-            Duration::days(0)
-        } else {
-            // need to grab next "weekday"
-            let next_week_day = self
-                .days
-                .iter()
-                .find(|day| *day > &current_day)
-                .unwrap_or(self.days.iter().find(|_| true).unwrap()); // loop to the first
-            current_day.duration_to(*next_week_day)
+    /// Repeats every `every` weeks (months, years...) only, counting from the one `anchor`
+    /// belongs to.
+    ///
+    /// Days rolled over from a period belong to it. An interval never landing on a period one of
+    /// the days falls in (e.g. every 12 months from a February on the 31st) never occurs.
+    pub fn with_interval(mut self, every: u32, anchor: NaiveDate) -> Result<Self, Error> {
+        self.interval = match every {
+            0 => return Err(Error::InvalidInterval),
+            1 => None,
+            every => Some(Interval { every, anchor }),
         };
-        apply_time(&(date.clone() + days_to_add), &self.time)
+        Ok(self.with_last())
     }
 
-    pub fn prev<T: TimeZone>(&self, date: &DateTime<T>) -> DateTime<T> {
-        let current_day: OrderedWeekday = date.weekday().into();
-        let days_to_add = if self.days.contains(&current_day) && date.time() > self.time {
-            // prev is current day :)
-            Duration::days(0)
-        } else {
-            // need to grab next "weekday"
-            let prev_week_day = self
-                .days
-                .iter()
-                .rev()
-                .find(|day| *day < &current_day)
-                .unwrap_or(self.days.iter().rev().find(|_| true).unwrap()); // loop to the last
-            current_day.duration_from(*prev_week_day)
-        };
-        apply_time(&(date.clone() + days_to_add), &self.time)
+    /// Occurs at or after the `start` wall time only.
+    pub fn with_start(mut self, start: NaiveDateTime) -> Self {
+        self.start = Some(start);
+        self.with_last()
+    }
+
+    /// Occurs at or before the `until` wall time only.
+    pub fn with_until(mut self, until: NaiveDateTime) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    /// Occurs `count` times only, counting from the start which must be set first.
+    ///
+    /// Occurrences are counted on the wall clock, like [`count_between`] does.
+    ///
+    /// [`count_between`]: Recurrence::count_between
+    pub fn with_count(mut self, count: u32) -> Result<Self, Error> {
+        if count == 0 {
+            return Err(Error::InvalidCount);
+        }
+        if self.start.is_none() {
+            return Err(Error::NoStart);
+        }
+        self.count = Some(count);
+        Ok(self.with_last())
+    }
+
+    /// Sets the day weeks start on, Monday by default, which recurrences repeating every n weeks
+    /// count weeks from.
+    pub fn with_week_start(mut self, week_start: WeekStart) -> Self {
+        self.week_start = week_start;
+        self.with_last()
+    }
+
+    pub fn week_start(&self) -> WeekStart {
+        self.week_start
+    }
+
+    pub fn start(&self) -> Option<NaiveDateTime> {
+        self.start
+    }
+
+    pub fn until(&self) -> Option<NaiveDateTime> {
+        self.until
+    }
+
+    pub fn count(&self) -> Option<u32> {
+        self.count
+    }
+
+    /// Walks to the `count`th occurrence once, rather than on every lookup.
+    fn with_last(mut self) -> Self {
+        self.last = nth_local(self.start, self.count, |local| self.next_local(local));
+        self
+    }
+
+    /// Wall time of the last occurrence, if bounded.
+    fn end(&self) -> Option<NaiveDateTime> {
+        end(self.last, self.until)
+    }
+
+    /// Sets what happens to occurrences skipped by a daylight saving time change, shifted
+    /// forward by default.
+    pub fn with_gap(mut self, gap: Gap) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Sets which of the two instants a wall time happening twice resolves to, the earliest by
+    /// default.
+    pub fn with_fold(mut self, fold: Fold) -> Self {
+        self.fold = fold;
+        self
+    }
+
+    fn in_phase(&self, date: NaiveDate) -> bool {
+        match self.interval {
+            None => true,
+            Some(interval) => {
+                let period = |date| D::period_with(date, self.week_start);
+                (period(date) - period(interval.anchor)).rem_euclid(interval.every as i64) == 0
+            }
+        }
+    }
+
+    /// Whether one of the days falls on `date`, in a period in phase.
+    fn falls_on(&self, date: NaiveDate) -> bool {
+        self.days
+            .iter()
+            .any(|day| day.falls_on(date) && self.in_phase(day.origin(date)))
+    }
+
+    /// Periods after which occurrences repeat, past which a search in phase gives up: some
+    /// intervals never land on a period some day falls in.
+    fn cycle(&self) -> i64 {
+        match self.interval {
+            None => D::CYCLE,
+            Some(interval) => {
+                let every = interval.every as i64;
+                every / gcd(every, D::CYCLE) * D::CYCLE
+            }
+        }
+    }
+
+    fn next_date(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let period = |date| D::period_with(date, self.week_start);
+        let limit = period(date) + self.cycle();
+        let mut date = D::next_date(&self.days, date)?;
+        while !self.falls_on(date) {
+            if period(date) > limit {
+                return None;
+            }
+            date = D::next_date(&self.days, date)?;
+        }
+        Some(date)
+    }
+
+    fn prev_date(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let period = |date| D::period_with(date, self.week_start);
+        let limit = period(date) - self.cycle();
+        let mut date = D::prev_date(&self.days, date)?;
+        while !self.falls_on(date) {
+            if period(date) < limit {
+                return None;
+            }
+            date = D::prev_date(&self.days, date)?;
+        }
+        Some(date)
+    }
+
+    /// First wall time strictly after `local`, `None` if there is none.
+    fn next_local(&self, local: NaiveDateTime) -> Option<NaiveDateTime> {
+        let today = local.date();
+        if self.falls_on(today) {
+            if let Some(time) = self.times.after(local.time()) {
+                // next is current day :)
+                return Some(today.and_time(time));
+            }
+        }
+        // need to grab next day
+        Some(self.next_date(today)?.and_time(self.times.first()))
+    }
+
+    /// Last wall time strictly before `local`, `None` if there is none.
+    fn prev_local(&self, local: NaiveDateTime) -> Option<NaiveDateTime> {
+        let today = local.date();
+        if self.falls_on(today) {
+            if let Some(time) = self.times.before(local.time()) {
+                // prev is current day :)
+                return Some(today.and_time(time));
+            }
+        }
+        // need to grab prev day
+        Some(self.prev_date(today)?.and_time(self.times.last()))
+    }
+}
+
+impl<D: RecurrentDay> Recurrent for Recurrence<D> {
+    /// First occurrence strictly after `date`.
+    ///
+    /// Occurrences are wall times in `date`'s time zone, resolved around daylight saving time
+    /// changes as set by [`with_gap`](Recurrence::with_gap) and
+    /// [`with_fold`](Recurrence::with_fold).
+    fn next<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>> {
+        dst::next(date, self.dst_policy(), self.start, self.end(), |local| {
+            self.next_local(local)
+        })
+    }
+
+    /// Last occurrence strictly before `date`, see [`next`](Self::next).
+    fn prev<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>> {
+        dst::prev(date, self.dst_policy(), self.start, self.end(), |local| {
+            self.prev_local(local)
+        })
+    }
+
+    fn dst_policy(&self) -> (Gap, Fold) {
+        (self.gap, self.fold)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Recurrence, Weekday, Weekdays};
-    use chrono::{DateTime, NaiveTime, Utc};
+    use crate::{Error, Recurrence, Recurrent, Weekday, Weekdays};
+    use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
 
     #[test]
     fn test() {
@@ -191,21 +803,180 @@ mod tests {
         );
     }
 
+    #[test]
+    fn times() {
+        let hms = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        let recurrence = Recurrence::new(
+            (Weekday::Mon, Weekday::Wed, Weekday::Fri),
+            (hms(17, 30), hms(9, 0)),
+        )
+        .unwrap();
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        // Monday
+        assert_eq!(
+            recurrence.next(&at("2020-08-31T08:00:00Z")).unwrap(),
+            at("2020-08-31T09:00:00Z")
+        );
+        assert_eq!(
+            recurrence.next(&at("2020-08-31T09:00:00Z")).unwrap(),
+            at("2020-08-31T17:30:00Z")
+        );
+        assert_eq!(
+            recurrence.next(&at("2020-08-31T17:30:00Z")).unwrap(),
+            at("2020-09-02T09:00:00Z")
+        );
+        assert_eq!(
+            recurrence.prev(&at("2020-08-31T17:30:00Z")).unwrap(),
+            at("2020-08-31T09:00:00Z")
+        );
+        assert_eq!(
+            recurrence.prev(&at("2020-08-31T09:00:00Z")).unwrap(),
+            at("2020-08-28T17:30:00Z")
+        );
+
+        let no_time: &[NaiveTime] = &[];
+        assert!(matches!(
+            Recurrence::new(Weekday::Mon, no_time),
+            Err(Error::NoTime)
+        ));
+    }
+
+    #[test]
+    fn bounds() {
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        let nine = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+        // every tuesday from march 1st until june 30th
+        let course = Recurrence::new(Weekday::Tue, nine)
+            .unwrap()
+            .with_start(NaiveDate::from_ymd_opt(2021, 3, 1).unwrap().and_time(nine))
+            .with_until(NaiveDate::from_ymd_opt(2021, 6, 30).unwrap().and_time(nine));
+        assert_eq!(
+            course.next(&at("2021-01-01T00:00:00Z")),
+            Some(at("2021-03-02T09:00:00Z"))
+        );
+        assert_eq!(course.prev(&at("2021-03-02T09:00:00Z")), None);
+        assert_eq!(
+            course.prev(&at("2022-01-01T00:00:00Z")),
+            Some(at("2021-06-29T09:00:00Z"))
+        );
+        assert_eq!(course.next(&at("2021-06-29T09:00:00Z")), None);
+        let (start, end) = (at("2021-01-01T00:00:00Z"), at("2022-01-01T00:00:00Z"));
+        assert_eq!(course.count_between(&start, &end, false), 18);
+        assert_eq!(course.between(&start, &end, false).count(), 18);
+
+        // 10 sessions
+        let sessions = Recurrence::new(Weekday::Tue, nine)
+            .unwrap()
+            .with_start(NaiveDate::from_ymd_opt(2021, 3, 2).unwrap().and_time(nine))
+            .with_count(10)
+            .unwrap();
+        assert_eq!(
+            sessions.occurrences_before(&end).next(),
+            Some(at("2021-05-04T09:00:00Z"))
+        );
+        assert_eq!(sessions.count_between(&start, &end, false), 10);
+        assert_eq!(
+            sessions.occurrences_after(&start).collect::<Vec<_>>(),
+            sessions.between(&start, &end, false).collect::<Vec<_>>()
+        );
+
+        assert!(matches!(
+            Recurrence::new(Weekday::Tue, nine).unwrap().with_count(10),
+            Err(Error::NoStart)
+        ));
+        assert!(matches!(sessions.with_count(0), Err(Error::InvalidCount)));
+
+        // the end is only walked to once
+        let minutes = Recurrence::every(
+            Weekday::Wed,
+            NaiveTime::MIN,
+            NaiveTime::from_hms_opt(23, 59, 0).unwrap(),
+            Duration::minutes(1),
+        )
+        .unwrap()
+        .with_start(
+            NaiveDate::from_ymd_opt(2021, 3, 3)
+                .unwrap()
+                .and_time(NaiveTime::MIN),
+        )
+        .with_count(20_000)
+        .unwrap();
+        assert_eq!(minutes.occurrences_after(&start).count(), 20_000);
+        assert_eq!(
+            minutes.occurrences_before(&end).next(),
+            Some(at("2021-06-02T21:19:00Z"))
+        );
+    }
+
+    #[test]
+    fn interval() {
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        // every other monday, in phase with 2020-08-31
+        let sprint = Recurrence::new(Weekday::Mon, NaiveTime::from_hms_opt(9, 0, 0).unwrap())
+            .unwrap()
+            .with_interval(2, NaiveDate::from_ymd_opt(2020, 8, 31).unwrap())
+            .unwrap();
+        assert_eq!(
+            sprint.next(&at("2020-08-31T09:00:00Z")).unwrap(),
+            at("2020-09-14T09:00:00Z")
+        );
+        assert_eq!(
+            sprint.next(&at("2020-09-07T08:00:00Z")).unwrap(),
+            at("2020-09-14T09:00:00Z")
+        );
+        assert_eq!(
+            sprint.prev(&at("2020-08-31T09:00:00Z")).unwrap(),
+            at("2020-08-17T09:00:00Z")
+        );
+        // the anchor only sets the phase, occurrences before it are still there
+        assert_eq!(
+            sprint.prev(&at("2020-08-24T09:00:00Z")).unwrap(),
+            at("2020-08-17T09:00:00Z")
+        );
+
+        let paydays = Recurrence::new(
+            (Weekday::Tue, Weekday::Fri),
+            NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+        )
+        .unwrap()
+        .with_interval(3, NaiveDate::from_ymd_opt(2020, 9, 4).unwrap())
+        .unwrap();
+        let start = at("2020-08-25T12:00:00Z");
+        for end in &[
+            "2020-09-04T12:00:00Z",
+            "2020-10-01T00:00:00Z",
+            "2021-02-02T12:00:00Z",
+        ] {
+            let end = at(end);
+            assert_eq!(
+                paydays.count_between(&start, &end, true),
+                paydays.between(&start, &end, true).count()
+            );
+        }
+
+        assert!(matches!(
+            sprint.with_interval(0, NaiveDate::from_ymd_opt(2020, 8, 31).unwrap()),
+            Err(Error::InvalidInterval)
+        ));
+    }
+
     fn test_next<T: Weekdays>(now: &str, days: T, (h, m, s): (u32, u32, u32), expect: &str) {
         // Sunday
         let now: DateTime<Utc> = now.parse().unwrap();
-        let w = Recurrence::new(days, NaiveTime::from_hms(h, m, s))
+        let w = Recurrence::new(days, NaiveTime::from_hms_opt(h, m, s).unwrap())
             .unwrap()
-            .next(&now);
+            .next(&now)
+            .unwrap();
         let e: DateTime<Utc> = expect.parse().unwrap();
         assert_eq!(w, e);
     }
 
     fn test_prev<T: Weekdays>(now: &str, days: T, (h, m, s): (u32, u32, u32), expect: &str) {
         let now: DateTime<Utc> = now.parse().unwrap();
-        let w = Recurrence::new(days, NaiveTime::from_hms(h, m, s))
+        let w = Recurrence::new(days, NaiveTime::from_hms_opt(h, m, s).unwrap())
             .unwrap()
-            .prev(&now);
+            .prev(&now)
+            .unwrap();
         let e: DateTime<Utc> = expect.parse().unwrap();
         assert_eq!(w, e);
     }
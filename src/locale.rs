@@ -0,0 +1,281 @@
+use crate::OrderedWeekday;
+
+/// A language names of weekdays and months are rendered and parsed in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Locale {
+    /// English.
+    #[default]
+    En,
+    /// French.
+    Fr,
+    /// German.
+    De,
+    /// Spanish.
+    Es,
+}
+
+/// Names of weekdays, from Monday, and months, from January.
+pub(crate) struct Names {
+    pub(crate) weekdays: [&'static str; 7],
+    pub(crate) short_weekdays: [&'static str; 7],
+    pub(crate) months: [&'static str; 12],
+    pub(crate) short_months: [&'static str; 12],
+}
+
+const EN: Names = Names {
+    weekdays: [
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+        "Thursday",
+        "Friday",
+        "Saturday",
+        "Sunday",
+    ],
+    short_weekdays: ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+    months: [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ],
+    short_months: [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ],
+};
+
+const FR: Names = Names {
+    weekdays: [
+        "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+    ],
+    short_weekdays: ["lun.", "mar.", "mer.", "jeu.", "ven.", "sam.", "dim."],
+    months: [
+        "janvier",
+        "février",
+        "mars",
+        "avril",
+        "mai",
+        "juin",
+        "juillet",
+        "août",
+        "septembre",
+        "octobre",
+        "novembre",
+        "décembre",
+    ],
+    short_months: [
+        "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.", "nov.",
+        "déc.",
+    ],
+};
+
+const DE: Names = Names {
+    weekdays: [
+        "Montag",
+        "Dienstag",
+        "Mittwoch",
+        "Donnerstag",
+        "Freitag",
+        "Samstag",
+        "Sonntag",
+    ],
+    short_weekdays: ["Mo.", "Di.", "Mi.", "Do.", "Fr.", "Sa.", "So."],
+    months: [
+        "Januar",
+        "Februar",
+        "März",
+        "April",
+        "Mai",
+        "Juni",
+        "Juli",
+        "August",
+        "September",
+        "Oktober",
+        "November",
+        "Dezember",
+    ],
+    short_months: [
+        "Jan.", "Feb.", "März", "Apr.", "Mai", "Juni", "Juli", "Aug.", "Sept.", "Okt.", "Nov.",
+        "Dez.",
+    ],
+};
+
+const ES: Names = Names {
+    weekdays: [
+        "lunes",
+        "martes",
+        "miércoles",
+        "jueves",
+        "viernes",
+        "sábado",
+        "domingo",
+    ],
+    short_weekdays: ["lun", "mar", "mié", "jue", "vie", "sáb", "dom"],
+    months: [
+        "enero",
+        "febrero",
+        "marzo",
+        "abril",
+        "mayo",
+        "junio",
+        "julio",
+        "agosto",
+        "septiembre",
+        "octubre",
+        "noviembre",
+        "diciembre",
+    ],
+    short_months: [
+        "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sep", "oct", "nov", "dic",
+    ],
+};
+
+pub(crate) const WEEKDAYS: [OrderedWeekday; 7] = [
+    OrderedWeekday::Mon,
+    OrderedWeekday::Tue,
+    OrderedWeekday::Wed,
+    OrderedWeekday::Thu,
+    OrderedWeekday::Fri,
+    OrderedWeekday::Sat,
+    OrderedWeekday::Sun,
+];
+
+/// Lowercased, without accents nor trailing dot, for names to match however they are typed.
+pub(crate) fn normalize(s: &str) -> String {
+    s.trim()
+        .trim_end_matches('.')
+        .chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            'à' | 'á' | 'â' | 'ä' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'í' | 'î' | 'ï' => 'i',
+            'ó' | 'ô' | 'ö' => 'o',
+            'ú' | 'û' | 'ù' | 'ü' => 'u',
+            'ç' => 'c',
+            'ñ' => 'n',
+            c => c,
+        })
+        .collect()
+}
+
+/// Index of `s` among `full` or `short` names.
+fn find(full: &[&str], short: &[&str], s: &str) -> Option<usize> {
+    let s = normalize(s);
+    full.iter()
+        .chain(short)
+        .position(|name| normalize(name) == s)
+        .map(|index| index % full.len())
+}
+
+impl Locale {
+    pub(crate) fn names(self) -> &'static Names {
+        match self {
+            Locale::En => &EN,
+            Locale::Fr => &FR,
+            Locale::De => &DE,
+            Locale::Es => &ES,
+        }
+    }
+
+    /// Full name of the given month (`1..=12`), `None` for other numbers.
+    pub fn month_name(self, month: u32) -> Option<&'static str> {
+        self.names()
+            .months
+            .get(month.checked_sub(1)? as usize)
+            .copied()
+    }
+
+    /// Abbreviated name of the given month (`1..=12`), `None` for other numbers.
+    pub fn month_abbreviation(self, month: u32) -> Option<&'static str> {
+        self.names()
+            .short_months
+            .get(month.checked_sub(1)? as usize)
+            .copied()
+    }
+
+    /// The month (`1..=12`) named `s` in full or abbreviated, ignoring case, accents and a
+    /// trailing dot.
+    pub fn month_from_name(self, s: &str) -> Option<u32> {
+        let names = self.names();
+        find(&names.months, &names.short_months, s).map(|index| index as u32 + 1)
+    }
+}
+
+impl OrderedWeekday {
+    /// Full name, e.g. `mercredi` in French.
+    pub fn name(self, locale: Locale) -> &'static str {
+        locale.names().weekdays[self as usize]
+    }
+
+    /// Abbreviated name, e.g. `mer.` in French.
+    pub fn abbreviation(self, locale: Locale) -> &'static str {
+        locale.names().short_weekdays[self as usize]
+    }
+
+    /// The weekday named `s` in full or abbreviated, ignoring case, accents and a trailing dot:
+    /// `miércoles`, `Miercoles` and `mié.` all are Wednesday in Spanish.
+    pub fn from_name(locale: Locale, s: &str) -> Option<Self> {
+        let names = locale.names();
+        find(&names.weekdays, &names.short_weekdays, s).map(|index| WEEKDAYS[index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Locale, OrderedWeekday};
+
+    #[test]
+    fn weekdays() {
+        assert_eq!(OrderedWeekday::Wed.name(Locale::Fr), "mercredi");
+        assert_eq!(OrderedWeekday::Sun.abbreviation(Locale::De), "So.");
+        assert_eq!(OrderedWeekday::Sat.name(Locale::Es), "sábado");
+        assert_eq!(
+            OrderedWeekday::from_name(Locale::Es, "Miercoles"),
+            Some(OrderedWeekday::Wed)
+        );
+        assert_eq!(
+            OrderedWeekday::from_name(Locale::Fr, "JEU"),
+            Some(OrderedWeekday::Thu)
+        );
+        assert_eq!(
+            OrderedWeekday::from_name(Locale::De, "donnerstag"),
+            Some(OrderedWeekday::Thu)
+        );
+        assert_eq!(OrderedWeekday::from_name(Locale::De, "Thursday"), None);
+        for locale in &[Locale::En, Locale::Fr, Locale::De, Locale::Es] {
+            for day in 0..7 {
+                let weekday = super::WEEKDAYS[day];
+                assert_eq!(
+                    OrderedWeekday::from_name(*locale, weekday.name(*locale)),
+                    Some(weekday)
+                );
+                assert_eq!(
+                    OrderedWeekday::from_name(*locale, weekday.abbreviation(*locale)),
+                    Some(weekday)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn months() {
+        assert_eq!(Locale::De.month_name(3), Some("März"));
+        assert_eq!(Locale::Fr.month_abbreviation(2), Some("févr."));
+        assert_eq!(Locale::En.month_name(13), None);
+        assert_eq!(Locale::En.month_name(0), None);
+        assert_eq!(Locale::Fr.month_from_name("aout"), Some(8));
+        assert_eq!(Locale::Es.month_from_name("Dic."), Some(12));
+        assert_eq!(Locale::De.month_from_name("Maerz"), None);
+    }
+}
@@ -0,0 +1,392 @@
+use crate::{days_in_month, Error, OrderedWeekday, Recurrence, RecurrentDay, Times};
+use chrono::{Datelike, NaiveDate, Weekday};
+
+/// What to do with a day of month that doesn't exist in a given month (e.g. the 31st of April).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum MissingDay {
+    /// No occurrence that month.
+    Skip,
+    /// Fall back to the last day of the month.
+    Clamp,
+    /// Roll over to the first day of the next month.
+    Rollover,
+}
+
+/// A day of month, counted from the start (`1..=31`) or from the end (`-1` is the last day).
+///
+/// Days counted from the end that don't exist in a month are always skipped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Ord, PartialOrd)]
+pub struct DayOfMonth {
+    day: i8,
+    missing: MissingDay,
+}
+
+impl DayOfMonth {
+    pub fn new(day: i8, missing: MissingDay) -> Result<Self, Error> {
+        if day == 0 || !(-31..=31).contains(&day) {
+            Err(Error::InvalidDayOfMonth(day))
+        } else {
+            Ok(DayOfMonth { day, missing })
+        }
+    }
+
+    /// The last day of the month.
+    pub fn last() -> Self {
+        DayOfMonth {
+            day: -1,
+            missing: MissingDay::Skip,
+        }
+    }
+
+    pub fn day(&self) -> i8 {
+        self.day
+    }
+
+    pub fn missing(&self) -> MissingDay {
+        self.missing
+    }
+}
+
+impl DayOfMonth {
+    /// The date this day falls on for the given month, rolling over to the next month if so.
+    pub(crate) fn resolve(&self, year: i32, month: u32) -> Option<NaiveDate> {
+        let len = days_in_month(year, month) as i8;
+        let day = if self.day < 0 {
+            len + 1 + self.day
+        } else if self.day <= len {
+            self.day
+        } else {
+            match self.missing {
+                MissingDay::Skip => return None,
+                MissingDay::Clamp => len,
+                MissingDay::Rollover => {
+                    return NaiveDate::from_ymd_opt(year, month, len as u32)?.succ_opt();
+                }
+            }
+        };
+        if day < 1 {
+            return None;
+        }
+        NaiveDate::from_ymd_opt(year, month, day as u32)
+    }
+}
+
+impl RecurrentDay for DayOfMonth {
+    fn falls_on(&self, date: NaiveDate) -> bool {
+        self.resolve(date.year(), date.month()) == Some(date) || self.rolls_over_to(date)
+    }
+
+    fn period(date: NaiveDate) -> i64 {
+        month_index(date)
+    }
+
+    const CYCLE: i64 = MONTH_CYCLE;
+
+    fn origin(&self, date: NaiveDate) -> NaiveDate {
+        match date.pred_opt() {
+            Some(prev) if self.rolls_over_to(date) => prev,
+            _ => date,
+        }
+    }
+}
+
+impl DayOfMonth {
+    /// Whether this day rolled over from the previous month falls on `date`.
+    fn rolls_over_to(&self, date: NaiveDate) -> bool {
+        let (year, month) = match date.month() {
+            1 => (date.year() - 1, 12),
+            month => (date.year(), month - 1),
+        };
+        date.day() == 1 && self.resolve(year, month) == Some(date)
+    }
+}
+
+/// The 400 years the Gregorian calendar repeats after, in months.
+const MONTH_CYCLE: i64 = 4800;
+
+fn month_index(date: NaiveDate) -> i64 {
+    date.year() as i64 * 12 + date.month0() as i64
+}
+
+impl Recurrence<DayOfMonth> {
+    /// Repeats on the given days of every month, see [`DayOfMonth`].
+    pub fn monthly<S: Times>(days: &[i8], times: S, missing: MissingDay) -> Result<Self, Error> {
+        let days = days
+            .iter()
+            .map(|day| DayOfMonth::new(*day, missing))
+            .collect::<Result<_, _>>()?;
+        Self::from_days(days, times)
+    }
+}
+
+/// The nth given weekday of the month, counted from the start (`1..=5`) or from the end (`-1`
+/// is the last one).
+///
+/// Months without an nth weekday (e.g. a fifth Friday) are skipped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Ord, PartialOrd)]
+pub struct NthWeekday {
+    nth: i8,
+    weekday: OrderedWeekday,
+}
+
+impl NthWeekday {
+    pub fn new(nth: i8, weekday: Weekday) -> Result<Self, Error> {
+        if nth == 0 || !(-5..=5).contains(&nth) {
+            Err(Error::InvalidNth(nth))
+        } else {
+            Ok(NthWeekday {
+                nth,
+                weekday: weekday.into(),
+            })
+        }
+    }
+
+    pub fn nth(&self) -> i8 {
+        self.nth
+    }
+
+    pub fn weekday(&self) -> OrderedWeekday {
+        self.weekday
+    }
+}
+
+impl NthWeekday {
+    /// The date this weekday falls on for the given month, if any.
+    pub(crate) fn resolve(&self, year: i32, month: u32) -> Option<NaiveDate> {
+        let len = days_in_month(year, month) as i64;
+        let weekday = self.weekday as i64;
+        let day = if self.nth > 0 {
+            let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+            let offset = (weekday - first.weekday().num_days_from_monday() as i64).rem_euclid(7);
+            1 + offset + 7 * (self.nth as i64 - 1)
+        } else {
+            let last = NaiveDate::from_ymd_opt(year, month, len as u32)?;
+            let offset = (last.weekday().num_days_from_monday() as i64 - weekday).rem_euclid(7);
+            len - offset - 7 * (-self.nth as i64 - 1)
+        };
+        if day < 1 || day > len {
+            None
+        } else {
+            NaiveDate::from_ymd_opt(year, month, day as u32)
+        }
+    }
+}
+
+impl RecurrentDay for NthWeekday {
+    fn falls_on(&self, date: NaiveDate) -> bool {
+        self.resolve(date.year(), date.month()) == Some(date)
+    }
+
+    fn period(date: NaiveDate) -> i64 {
+        month_index(date)
+    }
+
+    const CYCLE: i64 = MONTH_CYCLE;
+}
+
+impl Recurrence<NthWeekday> {
+    /// Repeats on the nth weekdays of every month, see [`NthWeekday`].
+    pub fn monthly_nth<S: Times>(days: &[(i8, Weekday)], times: S) -> Result<Self, Error> {
+        let days = days
+            .iter()
+            .map(|(nth, weekday)| NthWeekday::new(*nth, *weekday))
+            .collect::<Result<_, _>>()?;
+        Self::from_days(days, times)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, MissingDay, Recurrence, Recurrent};
+    use chrono::{DateTime, NaiveDate, NaiveTime, Utc, Weekday};
+
+    fn parse(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn next(days: &[i8], missing: MissingDay, now: &str) -> Vec<DateTime<Utc>> {
+        let eight = NaiveTime::from_hms_opt(8, 0, 0).unwrap();
+        Recurrence::monthly(days, eight, missing)
+            .unwrap()
+            .occurrences_after(&parse(now))
+            .take(4)
+            .collect()
+    }
+
+    #[test]
+    fn monthly() {
+        assert_eq!(
+            next(&[15, 1], MissingDay::Skip, "2020-08-15T08:00:00Z"),
+            vec![
+                parse("2020-09-01T08:00:00Z"),
+                parse("2020-09-15T08:00:00Z"),
+                parse("2020-10-01T08:00:00Z"),
+                parse("2020-10-15T08:00:00Z"),
+            ]
+        );
+        assert_eq!(
+            next(&[-1], MissingDay::Skip, "2020-01-31T08:00:00Z"),
+            vec![
+                parse("2020-02-29T08:00:00Z"),
+                parse("2020-03-31T08:00:00Z"),
+                parse("2020-04-30T08:00:00Z"),
+                parse("2020-05-31T08:00:00Z"),
+            ]
+        );
+        let recurrence = Recurrence::monthly(
+            &[1, 15],
+            NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+            MissingDay::Skip,
+        )
+        .unwrap();
+        assert_eq!(
+            recurrence.prev(&parse("2020-09-01T08:00:00Z")).unwrap(),
+            parse("2020-08-15T08:00:00Z")
+        );
+        assert_eq!(
+            recurrence.prev(&parse("2020-09-01T08:00:01Z")).unwrap(),
+            parse("2020-09-01T08:00:00Z")
+        );
+    }
+
+    #[test]
+    fn missing_days() {
+        assert_eq!(
+            next(&[31], MissingDay::Skip, "2021-01-31T08:00:00Z"),
+            vec![
+                parse("2021-03-31T08:00:00Z"),
+                parse("2021-05-31T08:00:00Z"),
+                parse("2021-07-31T08:00:00Z"),
+                parse("2021-08-31T08:00:00Z"),
+            ]
+        );
+        assert_eq!(
+            next(&[31], MissingDay::Clamp, "2021-01-31T08:00:00Z"),
+            vec![
+                parse("2021-02-28T08:00:00Z"),
+                parse("2021-03-31T08:00:00Z"),
+                parse("2021-04-30T08:00:00Z"),
+                parse("2021-05-31T08:00:00Z"),
+            ]
+        );
+        assert_eq!(
+            next(&[30], MissingDay::Rollover, "2021-01-30T08:00:00Z"),
+            vec![
+                parse("2021-03-01T08:00:00Z"),
+                parse("2021-03-30T08:00:00Z"),
+                parse("2021-04-30T08:00:00Z"),
+                parse("2021-05-30T08:00:00Z"),
+            ]
+        );
+        // clamping several days to the same date yields a single occurrence
+        assert_eq!(
+            next(&[29, 30, 31], MissingDay::Clamp, "2021-02-01T08:00:00Z")[..2],
+            [parse("2021-02-28T08:00:00Z"), parse("2021-03-29T08:00:00Z")]
+        );
+    }
+
+    #[test]
+    fn intervals() {
+        let eight = NaiveTime::from_hms_opt(8, 0, 0).unwrap();
+        let january = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+        let february = NaiveDate::from_ymd_opt(2021, 2, 1).unwrap();
+        // a rolled over day belongs to the month it rolled over from
+        let every_other = |anchor| {
+            Recurrence::monthly(&[30], eight, MissingDay::Rollover)
+                .unwrap()
+                .with_interval(2, anchor)
+                .unwrap()
+                .occurrences_after(&parse("2021-01-01T00:00:00Z"))
+                .take(3)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            every_other(january),
+            vec![
+                parse("2021-01-30T08:00:00Z"),
+                parse("2021-03-30T08:00:00Z"),
+                parse("2021-05-30T08:00:00Z"),
+            ]
+        );
+        assert_eq!(
+            every_other(february),
+            vec![
+                parse("2021-03-01T08:00:00Z"),
+                parse("2021-04-30T08:00:00Z"),
+                parse("2021-06-30T08:00:00Z"),
+            ]
+        );
+        // no February has a 31st
+        let never = Recurrence::monthly(&[31], eight, MissingDay::Skip)
+            .unwrap()
+            .with_interval(12, february)
+            .unwrap();
+        assert_eq!(never.next(&parse("2021-01-01T00:00:00Z")), None);
+        assert_eq!(never.prev(&parse("2021-01-01T00:00:00Z")), None);
+    }
+
+    #[test]
+    fn nth_weekday() {
+        let ten = NaiveTime::from_hms_opt(10, 0, 0).unwrap();
+        let patch_tuesday = Recurrence::monthly_nth(&[(2, Weekday::Tue)], ten).unwrap();
+        assert_eq!(
+            patch_tuesday.next(&parse("2020-09-08T10:00:00Z")).unwrap(),
+            parse("2020-10-13T10:00:00Z")
+        );
+        assert_eq!(
+            patch_tuesday.prev(&parse("2020-09-08T10:00:00Z")).unwrap(),
+            parse("2020-08-11T10:00:00Z")
+        );
+        let last_friday = Recurrence::monthly_nth(&[(-1, Weekday::Fri)], ten).unwrap();
+        assert_eq!(
+            last_friday
+                .occurrences_after(&parse("2020-01-01T00:00:00Z"))
+                .take(3)
+                .collect::<Vec<_>>(),
+            vec![
+                parse("2020-01-31T10:00:00Z"),
+                parse("2020-02-28T10:00:00Z"),
+                parse("2020-03-27T10:00:00Z"),
+            ]
+        );
+        // only a few months of 2021 have five Tuesdays
+        let fifth_tuesday = Recurrence::monthly_nth(&[(5, Weekday::Tue)], ten).unwrap();
+        assert_eq!(
+            fifth_tuesday
+                .occurrences_after(&parse("2021-01-01T00:00:00Z"))
+                .take(4)
+                .collect::<Vec<_>>(),
+            vec![
+                parse("2021-03-30T10:00:00Z"),
+                parse("2021-06-29T10:00:00Z"),
+                parse("2021-08-31T10:00:00Z"),
+                parse("2021-11-30T10:00:00Z"),
+            ]
+        );
+        // the fifth from the end is the first one in those months only
+        let fifth_last = Recurrence::monthly_nth(&[(-5, Weekday::Tue)], ten).unwrap();
+        assert_eq!(
+            fifth_last.next(&parse("2021-01-01T00:00:00Z")).unwrap(),
+            parse("2021-03-02T10:00:00Z")
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let eight = NaiveTime::from_hms_opt(8, 0, 0).unwrap();
+        assert!(matches!(
+            Recurrence::monthly(&[32], eight, MissingDay::Skip),
+            Err(Error::InvalidDayOfMonth(32))
+        ));
+        assert!(matches!(
+            Recurrence::monthly(&[], eight, MissingDay::Skip),
+            Err(Error::Empty)
+        ));
+        assert!(matches!(
+            Recurrence::monthly_nth(&[(6, Weekday::Mon)], eight),
+            Err(Error::InvalidNth(6))
+        ));
+    }
+}
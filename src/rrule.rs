@@ -0,0 +1,1466 @@
+//! [RFC 5545](https://tools.ietf.org/html/rfc5545#section-3.3.10) RRULE parsing and formatting.
+//!
+//! A recurrence is written as a bare RRULE value (`FREQ=WEEKLY;BYDAY=MO,WE;BYHOUR=9;...`) when it
+//! fits in one, or as `DTSTART` and `RRULE` content lines otherwise: a `DTSTART` sets the start
+//! and anchors intervals, and times or days that are not a plain product take several `RRULE`
+//! lines. A `COUNT` spanning several lines is written as the `UNTIL` it amounts to.
+//! A [`Schedule`] adds `EXDATE` and `RDATE` lines, dates or wall times.
+//! Missing days of month use the `SKIP` part of [RFC 7529](https://tools.ietf.org/html/rfc7529).
+//!
+//! Recurrences read and write floating wall times: a `DTSTART` in a `TZID` or in UTC, or a UTC
+//! `UNTIL`, is unsupported rather than dropped. With the `chrono-tz` feature, a
+//! [`Zoned`](crate::Zoned) recurrence reads them and writes its `DTSTART` in its `TZID`.
+
+use crate::yearly::YearlyDay;
+use crate::{
+    DayOfMonth, DayOfYear, Error, MissingDay, NthWeekday, OrderedWeekday, Recurrence, RecurrentDay,
+    Schedule, WeekStart,
+};
+#[cfg(feature = "chrono-tz")]
+use crate::{Fold, Gap, Zoned};
+#[cfg(feature = "chrono-tz")]
+use chrono::TimeZone;
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Weekday};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+
+const WEEKDAYS: [(&str, Weekday); 7] = [
+    ("MO", Weekday::Mon),
+    ("TU", Weekday::Tue),
+    ("WE", Weekday::Wed),
+    ("TH", Weekday::Thu),
+    ("FR", Weekday::Fri),
+    ("SA", Weekday::Sat),
+    ("SU", Weekday::Sun),
+];
+
+fn invalid(part: &str) -> Error {
+    Error::InvalidRRule(part.to_string())
+}
+
+fn unsupported(part: &str) -> Error {
+    Error::UnsupportedRRule(part.to_string())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Freq {
+    Secondly,
+    Minutely,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Freq {
+    fn name(self) -> &'static str {
+        match self {
+            Freq::Secondly => "SECONDLY",
+            Freq::Minutely => "MINUTELY",
+            Freq::Hourly => "HOURLY",
+            Freq::Daily => "DAILY",
+            Freq::Weekly => "WEEKLY",
+            Freq::Monthly => "MONTHLY",
+            Freq::Yearly => "YEARLY",
+        }
+    }
+}
+
+impl FromStr for Freq {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        [
+            Freq::Secondly,
+            Freq::Minutely,
+            Freq::Hourly,
+            Freq::Daily,
+            Freq::Weekly,
+            Freq::Monthly,
+            Freq::Yearly,
+        ]
+        .iter()
+        .copied()
+        .find(|freq| freq.name() == s)
+        .ok_or_else(|| invalid(&format!("FREQ={}", s)))
+    }
+}
+
+/// The parts of a single RRULE value.
+#[derive(Debug)]
+struct Rule {
+    freq: Freq,
+    interval: u32,
+    by_day: Vec<(Option<i8>, Weekday)>,
+    by_month_day: Vec<i8>,
+    by_month: Vec<u32>,
+    by_hour: Option<Vec<u32>>,
+    by_minute: Option<Vec<u32>>,
+    by_second: Option<Vec<u32>>,
+    skip: MissingDay,
+    count: Option<u32>,
+    until: Option<NaiveDateTime>,
+    utc_until: bool,
+    week_start: WeekStart,
+}
+
+fn numbers<T: FromStr + PartialOrd>(
+    part: &str,
+    value: &str,
+    range: std::ops::RangeInclusive<T>,
+) -> Result<Vec<T>, Error> {
+    value
+        .split(',')
+        .map(|n| match n.trim_start_matches('+').parse::<T>() {
+            Ok(n) if range.contains(&n) => Ok(n),
+            _ => Err(invalid(part)),
+        })
+        .collect()
+}
+
+/// A single value, for the parts that don't take lists.
+fn number<T: FromStr + PartialOrd + Copy>(
+    part: &str,
+    value: &str,
+    range: std::ops::RangeInclusive<T>,
+) -> Result<T, Error> {
+    match numbers(part, value, range)?.as_slice() {
+        [n] => Ok(*n),
+        _ => Err(invalid(part)),
+    }
+}
+
+fn by_day(part: &str, value: &str) -> Result<Vec<(Option<i8>, Weekday)>, Error> {
+    value
+        .split(',')
+        .map(|day| {
+            let split = day.len().checked_sub(2).ok_or_else(|| invalid(part))?;
+            if !day.is_char_boundary(split) {
+                return Err(invalid(part));
+            }
+            let (nth, name) = day.split_at(split);
+            let weekday = WEEKDAYS
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, w)| *w)
+                .ok_or_else(|| invalid(part))?;
+            let nth = match nth {
+                "" => None,
+                nth => Some(
+                    nth.trim_start_matches('+')
+                        .parse::<i8>()
+                        .map_err(|_| invalid(part))?,
+                ),
+            };
+            Ok((nth, weekday))
+        })
+        .collect()
+}
+
+impl FromStr for Rule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let mut freq = None;
+        let mut rule = Rule {
+            freq: Freq::Daily,
+            interval: 1,
+            by_day: vec![],
+            by_month_day: vec![],
+            by_month: vec![],
+            by_hour: None,
+            by_minute: None,
+            by_second: None,
+            skip: MissingDay::Skip,
+            count: None,
+            until: None,
+            utc_until: false,
+            week_start: WeekStart::default(),
+        };
+        let mut seen = BTreeSet::new();
+        for part in s.trim().split(';') {
+            let (name, value) = part.split_once('=').ok_or_else(|| invalid(part))?;
+            let name = name.to_ascii_uppercase();
+            let value = value.to_ascii_uppercase();
+            if !seen.insert(name.clone()) {
+                return Err(invalid(part));
+            }
+            match name.as_str() {
+                "FREQ" => freq = Some(value.parse()?),
+                "INTERVAL" => rule.interval = number(part, &value, 1..=u32::MAX)?,
+                "BYDAY" => rule.by_day = by_day(part, &value)?,
+                "BYMONTHDAY" => {
+                    rule.by_month_day = numbers(part, &value, -31..=31)?;
+                    if rule.by_month_day.contains(&0) {
+                        return Err(invalid(part));
+                    }
+                }
+                "BYMONTH" => rule.by_month = numbers(part, &value, 1..=12)?,
+                "BYHOUR" => rule.by_hour = Some(numbers(part, &value, 0..=23)?),
+                "BYMINUTE" => rule.by_minute = Some(numbers(part, &value, 0..=59)?),
+                "BYSECOND" => rule.by_second = Some(numbers(part, &value, 0..=59)?),
+                "WKST" => {
+                    rule.week_start = WEEKDAYS
+                        .iter()
+                        .find(|(name, _)| *name == value)
+                        .map(|(_, weekday)| WeekStart::new(*weekday))
+                        .ok_or_else(|| invalid(part))?
+                }
+                "RSCALE" if value == "GREGORIAN" => {}
+                "SKIP" => {
+                    rule.skip = match value.as_str() {
+                        "OMIT" => MissingDay::Skip,
+                        "BACKWARD" => MissingDay::Clamp,
+                        "FORWARD" => MissingDay::Rollover,
+                        _ => return Err(invalid(part)),
+                    }
+                }
+                "COUNT" => rule.count = Some(number(part, &value, 1..=u32::MAX)?),
+                "UNTIL" => {
+                    let (until, utc) = until(&value)?;
+                    rule.until = Some(until);
+                    rule.utc_until = utc;
+                }
+                "RSCALE" | "BYSETPOS" | "BYWEEKNO" | "BYYEARDAY" => return Err(unsupported(part)),
+                _ => return Err(invalid(part)),
+            }
+        }
+        rule.freq = freq.ok_or_else(|| invalid(s))?;
+        Ok(rule)
+    }
+}
+
+/// A date or date-time value, and whether it is in UTC.
+fn date_time(value: &str) -> Result<(NaiveDateTime, bool), Error> {
+    let (local, utc) = match value.strip_suffix('Z') {
+        Some(local) => (local, true),
+        None => (value, false),
+    };
+    let date_time = NaiveDateTime::parse_from_str(local, "%Y%m%dT%H%M%S")
+        .or_else(|_| {
+            NaiveDate::parse_from_str(local, "%Y%m%d").map(|date| date.and_time(NaiveTime::MIN))
+        })
+        .map_err(|_| invalid(value))?;
+    Ok((date_time, utc))
+}
+
+/// A floating date or date-time value: UTC ones are not wall times.
+fn floating(value: &str) -> Result<NaiveDateTime, Error> {
+    match date_time(value)? {
+        (date_time, false) => Ok(date_time),
+        (_, true) => Err(unsupported(value)),
+    }
+}
+
+/// A date `UNTIL` includes the whole day.
+fn until(value: &str) -> Result<(NaiveDateTime, bool), Error> {
+    match NaiveDate::parse_from_str(value, "%Y%m%d") {
+        Ok(date) => Ok((date.and_hms_opt(23, 59, 59).unwrap(), false)),
+        Err(_) => date_time(value),
+    }
+}
+
+/// The `DTSTART`, its time zone (`UTC` for a `Z` value) and the rules of RRULE content lines.
+struct Lines {
+    start: Option<NaiveDateTime>,
+    timezone: Option<String>,
+    rules: Vec<Rule>,
+}
+
+/// Parses a bare RRULE value or `DTSTART`/`RRULE` content lines.
+fn parse_lines(s: &str) -> Result<Lines, Error> {
+    let s = s.trim();
+    let mut lines = Lines {
+        start: None,
+        timezone: None,
+        rules: vec![],
+    };
+    if !s.contains(':') {
+        lines.rules.push(s.parse()?);
+        return Ok(lines);
+    }
+    for line in s.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let (name, value) = line.split_once(':').ok_or_else(|| invalid(line))?;
+        let mut parameters = name.split(';');
+        match parameters.next().unwrap().to_ascii_uppercase().as_str() {
+            "DTSTART" => {
+                let (start, utc) = date_time(value)?;
+                lines.start = Some(start);
+                if utc {
+                    lines.timezone = Some("UTC".to_string());
+                }
+                for parameter in parameters {
+                    match parameter.split_once('=') {
+                        Some((name, tzid)) if name.eq_ignore_ascii_case("TZID") && !utc => {
+                            lines.timezone = Some(tzid.to_string())
+                        }
+                        Some((name, _)) if name.eq_ignore_ascii_case("VALUE") => {}
+                        _ => return Err(invalid(line)),
+                    }
+                }
+            }
+            "RRULE" if parameters.next().is_none() => lines.rules.push(value.parse()?),
+            name => return Err(unsupported(name)),
+        }
+    }
+    if lines.rules.is_empty() {
+        return Err(invalid(s));
+    }
+    Ok(lines)
+}
+
+/// Times of day a rule occurs at.
+fn rule_times(rule: &Rule, start: Option<NaiveDateTime>) -> Result<Vec<NaiveTime>, Error> {
+    let start = start.map(|start| start.time()).unwrap_or(NaiveTime::MIN);
+    let limit =
+        |by: &Option<Vec<u32>>, value: u32| by.as_ref().is_none_or(|by| by.contains(&value));
+    let expand = |by: &Option<Vec<u32>>, value: u32| by.clone().unwrap_or_else(|| vec![value]);
+    let unit = match rule.freq {
+        Freq::Secondly => 1,
+        Freq::Minutely => 60,
+        Freq::Hourly => 3600,
+        _ => {
+            let mut times = vec![];
+            for hour in expand(&rule.by_hour, start.hour()) {
+                for minute in expand(&rule.by_minute, start.minute()) {
+                    for second in expand(&rule.by_second, start.second()) {
+                        times.extend(NaiveTime::from_hms_opt(hour, minute, second));
+                    }
+                }
+            }
+            return Ok(times);
+        }
+    };
+    // the steps must repeat day to day
+    let step = unit * rule.interval;
+    if 86_400 % step != 0 {
+        return Err(unsupported(&format!("INTERVAL={}", rule.interval)));
+    }
+    let mut times = vec![];
+    let first = start.num_seconds_from_midnight() % step;
+    for seconds in (first..86_400).step_by(step as usize) {
+        let time = NaiveTime::from_num_seconds_from_midnight_opt(seconds, 0).unwrap();
+        if !limit(&rule.by_hour, time.hour()) {
+            continue;
+        }
+        let minutes = match rule.freq {
+            Freq::Hourly => expand(&rule.by_minute, time.minute()),
+            _ if limit(&rule.by_minute, time.minute()) => vec![time.minute()],
+            _ => continue,
+        };
+        let seconds = match rule.freq {
+            Freq::Secondly if !limit(&rule.by_second, time.second()) => continue,
+            Freq::Secondly => vec![time.second()],
+            _ => expand(&rule.by_second, time.second()),
+        };
+        for minute in &minutes {
+            for second in &seconds {
+                times.extend(NaiveTime::from_hms_opt(time.hour(), *minute, *second));
+            }
+        }
+    }
+    Ok(times)
+}
+
+/// Rule parts of several times of day, each a product of hours, minutes and seconds.
+fn time_parts(times: &BTreeSet<NaiveTime>) -> Vec<String> {
+    let mut by_hour: BTreeMap<u32, BTreeSet<(u32, u32)>> = BTreeMap::new();
+    for time in times {
+        by_hour
+            .entry(time.hour())
+            .or_default()
+            .insert((time.second(), time.minute()));
+    }
+    let mut hours_by_pairs: BTreeMap<BTreeSet<(u32, u32)>, Vec<u32>> = BTreeMap::new();
+    for (hour, pairs) in by_hour {
+        hours_by_pairs.entry(pairs).or_default().push(hour);
+    }
+    let mut parts = vec![];
+    for (pairs, hours) in hours_by_pairs {
+        let mut minutes_by_second: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+        for (second, minute) in pairs {
+            minutes_by_second.entry(second).or_default().push(minute);
+        }
+        let mut seconds_by_minutes: BTreeMap<Vec<u32>, Vec<u32>> = BTreeMap::new();
+        for (second, minutes) in minutes_by_second {
+            seconds_by_minutes.entry(minutes).or_default().push(second);
+        }
+        for (minutes, seconds) in seconds_by_minutes {
+            parts.push(format!(
+                "BYHOUR={};BYMINUTE={};BYSECOND={}",
+                join(&hours),
+                join(&minutes),
+                join(&seconds)
+            ));
+        }
+    }
+    parts
+}
+
+fn join<T: ToString>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn skip_part(missing: MissingDay) -> &'static str {
+    match missing {
+        MissingDay::Skip => "",
+        MissingDay::Clamp => ";RSCALE=GREGORIAN;SKIP=BACKWARD",
+        MissingDay::Rollover => ";RSCALE=GREGORIAN;SKIP=FORWARD",
+    }
+}
+
+fn weekday_name(weekday: Weekday) -> &'static str {
+    WEEKDAYS[weekday.num_days_from_monday() as usize].0
+}
+
+fn nth_weekday_name(nth: NthWeekday) -> String {
+    format!("{}{}", nth.nth(), weekday_name(nth.weekday().into()))
+}
+
+/// Every nth weekday of the month, for a `BYDAY` without ordinal.
+fn every_nth(weekday: Weekday) -> impl Iterator<Item = NthWeekday> {
+    (1..=5).map(move |nth| NthWeekday::new(nth, weekday).unwrap())
+}
+
+/// Days a recurrence kind reads from and writes to RRULEs.
+trait RRuleDay: RecurrentDay {
+    fn from_rule(rule: &Rule, start: Option<NaiveDateTime>) -> Result<BTreeSet<Self>, Error>;
+
+    /// `FREQ` and day parts, one entry per needed RRULE.
+    fn to_parts(days: &BTreeSet<Self>) -> Vec<(Freq, String)>;
+}
+
+fn start_date(start: Option<NaiveDateTime>, freq: Freq) -> Result<NaiveDate, Error> {
+    start
+        .map(|start| start.date())
+        .ok_or_else(|| invalid(&format!("FREQ={} without day nor DTSTART", freq.name())))
+}
+
+fn check_freq(rule: &Rule, freqs: &[Freq]) -> Result<(), Error> {
+    if freqs.contains(&rule.freq) {
+        Ok(())
+    } else {
+        Err(unsupported(&format!("FREQ={}", rule.freq.name())))
+    }
+}
+
+impl RRuleDay for OrderedWeekday {
+    fn from_rule(rule: &Rule, start: Option<NaiveDateTime>) -> Result<BTreeSet<Self>, Error> {
+        use Freq::*;
+        check_freq(rule, &[Secondly, Minutely, Hourly, Daily, Weekly])?;
+        if rule.freq == Daily && rule.interval > 1 {
+            return Err(unsupported(&format!("INTERVAL={}", rule.interval)));
+        }
+        if !rule.by_month_day.is_empty() {
+            return Err(unsupported("BYMONTHDAY"));
+        }
+        if !rule.by_month.is_empty() {
+            return Err(unsupported("BYMONTH"));
+        }
+        if rule.by_day.iter().any(|(nth, _)| nth.is_some()) {
+            return Err(unsupported("BYDAY"));
+        }
+        Ok(if !rule.by_day.is_empty() {
+            rule.by_day.iter().map(|(_, day)| (*day).into()).collect()
+        } else if rule.freq == Weekly {
+            std::iter::once(start_date(start, rule.freq)?.weekday().into()).collect()
+        } else {
+            WEEKDAYS.iter().map(|(_, day)| (*day).into()).collect()
+        })
+    }
+
+    fn to_parts(days: &BTreeSet<Self>) -> Vec<(Freq, String)> {
+        let days: Vec<_> = days.iter().map(|d| weekday_name((*d).into())).collect();
+        vec![(Freq::Weekly, format!("BYDAY={}", days.join(",")))]
+    }
+}
+
+impl RRuleDay for DayOfMonth {
+    fn from_rule(rule: &Rule, start: Option<NaiveDateTime>) -> Result<BTreeSet<Self>, Error> {
+        check_freq(rule, &[Freq::Monthly])?;
+        if !rule.by_day.is_empty() {
+            return Err(unsupported("BYDAY"));
+        }
+        if !rule.by_month.is_empty() {
+            return Err(unsupported("BYMONTH"));
+        }
+        if rule.by_month_day.is_empty() {
+            let day = start_date(start, rule.freq)?.day() as i8;
+            return Ok(std::iter::once(DayOfMonth::new(day, rule.skip)?).collect());
+        }
+        rule.by_month_day
+            .iter()
+            .map(|day| DayOfMonth::new(*day, rule.skip))
+            .collect()
+    }
+
+    fn to_parts(days: &BTreeSet<Self>) -> Vec<(Freq, String)> {
+        let mut by_missing: BTreeMap<MissingDay, Vec<i8>> = BTreeMap::new();
+        for day in days {
+            by_missing.entry(day.missing()).or_default().push(day.day());
+        }
+        by_missing
+            .into_iter()
+            .map(|(missing, days)| {
+                let part = format!("BYMONTHDAY={}{}", join(&days), skip_part(missing));
+                (Freq::Monthly, part)
+            })
+            .collect()
+    }
+}
+
+impl RRuleDay for NthWeekday {
+    fn from_rule(rule: &Rule, _: Option<NaiveDateTime>) -> Result<BTreeSet<Self>, Error> {
+        check_freq(rule, &[Freq::Monthly])?;
+        if !rule.by_month_day.is_empty() {
+            return Err(unsupported("BYMONTHDAY"));
+        }
+        if !rule.by_month.is_empty() {
+            return Err(unsupported("BYMONTH"));
+        }
+        if rule.by_day.is_empty() {
+            return Err(invalid("FREQ=MONTHLY without BYDAY"));
+        }
+        let mut days = BTreeSet::new();
+        for (nth, weekday) in &rule.by_day {
+            match nth {
+                Some(nth) => {
+                    days.insert(NthWeekday::new(*nth, *weekday)?);
+                }
+                None => days.extend(every_nth(*weekday)),
+            }
+        }
+        Ok(days)
+    }
+
+    fn to_parts(days: &BTreeSet<Self>) -> Vec<(Freq, String)> {
+        let days: Vec<_> = days.iter().map(|d| nth_weekday_name(*d)).collect();
+        vec![(Freq::Monthly, format!("BYDAY={}", days.join(",")))]
+    }
+}
+
+impl RRuleDay for DayOfYear {
+    fn from_rule(rule: &Rule, start: Option<NaiveDateTime>) -> Result<BTreeSet<Self>, Error> {
+        check_freq(rule, &[Freq::Yearly])?;
+        if !rule.by_day.is_empty() && !rule.by_month_day.is_empty() {
+            return Err(unsupported("BYDAY with BYMONTHDAY"));
+        }
+        if !rule.by_day.is_empty() && rule.by_month.is_empty() {
+            return Err(unsupported("BYDAY without BYMONTH"));
+        }
+        if rule.by_month_day.iter().any(|day| *day < 0) {
+            return Err(unsupported("BYMONTHDAY"));
+        }
+        let months = if rule.by_month.is_empty() {
+            vec![start_date(start, rule.freq)?.month()]
+        } else {
+            rule.by_month.clone()
+        };
+        let mut days = BTreeSet::new();
+        for month in months {
+            for (nth, weekday) in &rule.by_day {
+                match nth {
+                    Some(nth) => {
+                        days.insert(DayOfYear::nth_weekday(month, *nth, *weekday)?);
+                    }
+                    None => {
+                        for nth in every_nth(*weekday) {
+                            days.insert(DayOfYear::nth_weekday(month, nth.nth(), *weekday)?);
+                        }
+                    }
+                }
+            }
+            for day in &rule.by_month_day {
+                days.insert(DayOfYear::date(month, *day as u32, rule.skip)?);
+            }
+            if rule.by_day.is_empty() && rule.by_month_day.is_empty() {
+                let day = start_date(start, rule.freq)?.day();
+                days.insert(DayOfYear::date(month, day, rule.skip)?);
+            }
+        }
+        Ok(days)
+    }
+
+    fn to_parts(days: &BTreeSet<Self>) -> Vec<(Freq, String)> {
+        let mut months_by_day: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+        for day in days {
+            let part = match day.day() {
+                YearlyDay::Date(date) => {
+                    format!("BYMONTHDAY={}{}", date.day(), skip_part(date.missing()))
+                }
+                YearlyDay::Nth(nth) => format!("BYDAY={}", nth_weekday_name(nth)),
+            };
+            months_by_day.entry(part).or_default().push(day.month());
+        }
+        months_by_day
+            .into_iter()
+            .map(|(part, months)| (Freq::Yearly, format!("BYMONTH={};{}", join(&months), part)))
+            .collect()
+    }
+}
+
+/// Parses floating wall times only: a recurrence has no time zone.
+fn parse<D: RRuleDay>(s: &str) -> Result<Recurrence<D>, Error> {
+    let lines = parse_lines(s)?;
+    if let Some(timezone) = lines.timezone {
+        return Err(unsupported(&format!("DTSTART in {}", timezone)));
+    }
+    for rule in &lines.rules {
+        if let (Some(until), true) = (rule.until, rule.utc_until) {
+            return Err(unsupported(&format!("UNTIL={}Z", format_date_time(until))));
+        }
+    }
+    build(lines.start, &lines.rules)
+}
+
+/// Parses the wall times of `DTSTART` in its time zone, and `UTC` ones of `UNTIL` into it.
+#[cfg(feature = "chrono-tz")]
+fn parse_zoned<D: RRuleDay>(s: &str) -> Result<Zoned<D, chrono_tz::Tz>, Error> {
+    let mut lines = parse_lines(s)?;
+    let name = lines
+        .timezone
+        .ok_or_else(|| unsupported("DTSTART without TZID"))?;
+    let timezone: chrono_tz::Tz = name.parse().map_err(|_| Error::UnknownTimeZone(name))?;
+    for rule in &mut lines.rules {
+        if let (Some(until), true) = (rule.until, rule.utc_until) {
+            let until = chrono::Utc
+                .from_utc_datetime(&until)
+                .with_timezone(&timezone);
+            rule.until = Some(until.naive_local());
+        }
+    }
+    Ok(build(lines.start, &lines.rules)?.in_timezone(timezone))
+}
+
+fn build<D: RRuleDay>(
+    start: Option<NaiveDateTime>,
+    rules: &[Rule],
+) -> Result<Recurrence<D>, Error> {
+    let mut occurrences = BTreeSet::new();
+    for rule in rules {
+        let times = rule_times(rule, start)?;
+        for day in D::from_rule(rule, start)? {
+            occurrences.extend(times.iter().map(|time| (day, *time)));
+        }
+    }
+    let days: BTreeSet<D> = occurrences.iter().map(|(day, _)| *day).collect();
+    let times: BTreeSet<NaiveTime> = occurrences.iter().map(|(_, time)| *time).collect();
+    if occurrences.len() != days.len() * times.len() {
+        return Err(unsupported("RRULE days and times not in common"));
+    }
+    let interval = match rules[0].freq {
+        // spent on the times of day
+        Freq::Secondly | Freq::Minutely | Freq::Hourly => 1,
+        _ => rules[0].interval,
+    };
+    if rules.iter().any(|rule| rule.interval != rules[0].interval) {
+        return Err(unsupported("RRULE intervals not in common"));
+    }
+    if rules
+        .iter()
+        .any(|rule| rule.week_start != rules[0].week_start)
+    {
+        return Err(unsupported("RRULE WKST not in common"));
+    }
+    if rules.iter().any(|rule| rule.until != rules[0].until) {
+        return Err(unsupported("RRULE UNTIL not in common"));
+    }
+    let count = rules[0].count;
+    if count.is_some() && rules.len() > 1 {
+        return Err(unsupported("COUNT with several RRULEs"));
+    }
+    let mut recurrence = Recurrence::from_days(days, times)?.with_week_start(rules[0].week_start);
+    if let Some(start) = start {
+        recurrence = recurrence.with_start(start);
+    }
+    if let Some(until) = rules[0].until {
+        recurrence = recurrence.with_until(until);
+    }
+    if let Some(count) = count {
+        recurrence = recurrence
+            .with_count(count)
+            .map_err(|_| invalid("COUNT without DTSTART"))?;
+    }
+    if interval == 1 {
+        return Ok(recurrence);
+    }
+    let anchor = start.ok_or_else(|| unsupported("INTERVAL without DTSTART"))?;
+    recurrence.with_interval(interval, anchor.date())
+}
+
+fn format_date_time(date: NaiveDateTime) -> impl fmt::Display {
+    date.format("%Y%m%dT%H%M%S")
+}
+
+/// The zone `DTSTART` is written in as a `TZID`, `UNTIL` being written in UTC.
+struct Zone<'a> {
+    tzid: &'a str,
+    to_utc: &'a dyn Fn(NaiveDateTime) -> Option<NaiveDateTime>,
+}
+
+/// Writes floating wall times, or ones in `zone` which needs a `DTSTART`.
+///
+/// The anchor of an interval without a start is written as the `DTSTART`, which parses back as a
+/// start.
+fn write<D: RRuleDay>(
+    recurrence: &Recurrence<D>,
+    zone: Option<&Zone<'_>>,
+) -> Result<String, Error> {
+    let interval = recurrence
+        .interval
+        .map(|interval| format!(";INTERVAL={}", interval.every))
+        .unwrap_or_default();
+    let times = recurrence.times.to_set();
+    let time_parts = time_parts(&times);
+    let day_parts = D::to_parts(&recurrence.days);
+    let single = day_parts.len() * time_parts.len() == 1;
+    let bounds = match (recurrence.count, recurrence.until) {
+        (Some(count), None) if single => format!(";COUNT={}", count),
+        _ => match (recurrence.end(), zone) {
+            (None, _) => String::new(),
+            (Some(end), None) => format!(";UNTIL={}", format_date_time(end)),
+            (Some(end), Some(zone)) => {
+                let end = (zone.to_utc)(end).ok_or_else(|| invalid("UNTIL"))?;
+                format!(";UNTIL={}Z", format_date_time(end))
+            }
+        },
+    };
+    let week_start = match recurrence.week_start {
+        WeekStart::MONDAY => String::new(),
+        week_start => format!(";WKST={}", weekday_name(week_start.weekday().into())),
+    };
+    let mut rules = vec![];
+    for (freq, day_part) in day_parts {
+        for time_part in &time_parts {
+            rules.push(format!(
+                "FREQ={}{}{};{};{}{}",
+                freq.name(),
+                interval,
+                bounds,
+                day_part,
+                time_part,
+                week_start
+            ));
+        }
+    }
+    let anchor = recurrence
+        .interval
+        .map(|interval| interval.anchor.and_time(recurrence.times.first()));
+    let mut lines = match (recurrence.start.or(anchor), zone) {
+        (None, None) if rules.len() == 1 => return Ok(rules.remove(0)),
+        (None, None) => vec![],
+        (Some(start), None) => vec![format!("DTSTART:{}", format_date_time(start))],
+        (Some(start), Some(zone)) => vec![format!(
+            "DTSTART;TZID={}:{}",
+            zone.tzid,
+            format_date_time(start)
+        )],
+        (None, Some(_)) => return Err(unsupported("TZID without DTSTART")),
+    };
+    lines.extend(rules.iter().map(|rule| format!("RRULE:{}", rule)));
+    Ok(lines.join("\n"))
+}
+
+/// Writes `DTSTART` in the zone's `TZID`, which needs a start.
+#[cfg(feature = "chrono-tz")]
+fn write_zoned<D: RRuleDay>(zoned: &Zoned<D, chrono_tz::Tz>) -> Result<String, Error> {
+    let recurrence = zoned.recurrence();
+    if recurrence.start.is_none() {
+        return Err(unsupported("TZID without DTSTART"));
+    }
+    let timezone = zoned.timezone();
+    let to_utc = |local| {
+        crate::dst::resolve(timezone, local, Gap::Shift, Fold::Latest).map(|utc| utc.naive_utc())
+    };
+    let zone = Zone {
+        tzid: timezone.name(),
+        to_utc: &to_utc,
+    };
+    write(recurrence, Some(&zone))
+}
+
+fn parse_schedule<D: RRuleDay>(s: &str) -> Result<Schedule<D>, Error> {
+    let mut rules = vec![];
+    let mut excluded = vec![];
+    let mut added = vec![];
+    for line in s.trim().lines().map(str::trim) {
+        let (name, value) = line.split_once(':').unwrap_or((line, ""));
+        let mut parameters = name.split(';');
+        let dates = match parameters.next().unwrap().to_ascii_uppercase().as_str() {
+            "EXDATE" => &mut excluded,
+            "RDATE" => &mut added,
+            _ => {
+                rules.push(line);
+                continue;
+            }
+        };
+        // dates are wall times, as the recurrence's are
+        for parameter in parameters {
+            match parameter.split_once('=') {
+                Some((name, _)) if name.eq_ignore_ascii_case("VALUE") => {}
+                _ => return Err(unsupported(name)),
+            }
+        }
+        dates.extend(value.split(','));
+    }
+    let mut schedule = Schedule::new(parse(&rules.join("\n"))?);
+    for value in excluded {
+        match NaiveDate::parse_from_str(value, "%Y%m%d") {
+            Ok(date) => schedule.exclude_date(date),
+            Err(_) => schedule.exclude(floating(value)?),
+        }
+    }
+    for value in added {
+        if value.len() == 8 {
+            return Err(unsupported(&format!("RDATE:{}", value)));
+        }
+        schedule.add(floating(value)?);
+    }
+    Ok(schedule)
+}
+
+fn write_schedule<D: RRuleDay>(schedule: &Schedule<D>, f: &mut fmt::Formatter<'_>) -> fmt::Result
+where
+    Recurrence<D>: fmt::Display,
+{
+    let rules = schedule.recurrence().to_string();
+    let excluded_dates: Vec<_> = schedule
+        .excluded_dates()
+        .iter()
+        .map(|date| date.format("%Y%m%d").to_string())
+        .collect();
+    let format = |dates: &BTreeSet<NaiveDateTime>| -> Vec<_> {
+        dates
+            .iter()
+            .map(|date| format_date_time(*date).to_string())
+            .collect()
+    };
+    // moved occurrences cancel their original and take place where they have been moved
+    let moved = schedule.rescheduled().iter().filter(|(original, _)| {
+        !schedule.excluded().contains(original)
+            && !schedule.excluded_dates().contains(&original.date())
+    });
+    let mut excluded = schedule.excluded().clone();
+    let mut added = schedule.added().clone();
+    for (original, moved) in moved {
+        excluded.insert(*original);
+        added.insert(*moved);
+    }
+    let excluded = format(&excluded);
+    let added = format(&added);
+    if excluded_dates.is_empty() && excluded.is_empty() && added.is_empty() {
+        return f.write_str(&rules);
+    }
+    if rules.contains(':') {
+        f.write_str(&rules)?;
+    } else {
+        write!(f, "RRULE:{}", rules)?;
+    }
+    if !excluded_dates.is_empty() {
+        write!(f, "\nEXDATE;VALUE=DATE:{}", excluded_dates.join(","))?;
+    }
+    if !excluded.is_empty() {
+        write!(f, "\nEXDATE:{}", excluded.join(","))?;
+    }
+    if !added.is_empty() {
+        write!(f, "\nRDATE:{}", added.join(","))?;
+    }
+    Ok(())
+}
+
+macro_rules! rrule {
+    ($($day:ty),*) => {$(
+        /// Formats as a bare RFC 5545 RRULE value, or as `DTSTART` and `RRULE` content lines
+        /// when an interval or several rules are needed.
+        ///
+        /// An interval without a start is written with its anchor as the `DTSTART`, which parses
+        /// back as a start too: [`to_rrule`](Recurrence::to_rrule) fails instead.
+        impl fmt::Display for Recurrence<$day> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&write(self, None).map_err(|_| fmt::Error)?)
+            }
+        }
+
+        impl Recurrence<$day> {
+            /// Formats as [`Display`](fmt::Display) does, failing when parsing it back would not
+            /// give the same recurrence: RRULEs anchor intervals on their start.
+            pub fn to_rrule(&self) -> Result<String, Error> {
+                if self.interval.is_some() && self.start.is_none() {
+                    return Err(unsupported("INTERVAL without DTSTART"));
+                }
+                write(self, None)
+            }
+        }
+
+        /// Parses a bare RFC 5545 RRULE value, or `DTSTART` and `RRULE` content lines, of
+        /// floating wall times: a `TZID` or UTC `DTSTART` or `UNTIL` is unsupported, a
+        /// [`Zoned`](crate::Zoned) recurrence parses them.
+        impl FromStr for Recurrence<$day> {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self, Error> {
+                parse(s)
+            }
+        }
+
+        /// Formats as the RRULE of its recurrence, followed by `EXDATE` and `RDATE` content
+        /// lines when needed.
+        ///
+        /// Moved occurrences are written as their original one excluded and their new one added,
+        /// losing their identity: they take their own `RECURRENCE-ID` components in RFC 5545.
+        impl fmt::Display for Schedule<$day> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write_schedule(self, f)
+            }
+        }
+
+        impl Schedule<$day> {
+            /// Formats as [`Display`](fmt::Display) does, failing when parsing it back would not
+            /// give the same schedule, e.g. with moved occurrences.
+            pub fn to_rrule(&self) -> Result<String, Error> {
+                if !self.rescheduled().is_empty() {
+                    return Err(unsupported("RECURRENCE-ID"));
+                }
+                self.recurrence().to_rrule()?;
+                Ok(self.to_string())
+            }
+        }
+
+        /// Parses an RRULE, as its recurrence does, along with `EXDATE` and `RDATE` content
+        /// lines.
+        impl FromStr for Schedule<$day> {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self, Error> {
+                parse_schedule(s)
+            }
+        }
+
+        /// Parses `DTSTART` and `RRULE` content lines, the `DTSTART` in a `TZID` or in UTC.
+        #[cfg(feature = "chrono-tz")]
+        impl FromStr for Zoned<$day, chrono_tz::Tz> {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self, Error> {
+                parse_zoned(s)
+            }
+        }
+
+        #[cfg(feature = "chrono-tz")]
+        impl Zoned<$day, chrono_tz::Tz> {
+            /// Formats as `DTSTART` in the zone's `TZID` and `RRULE` content lines, `UNTIL` in
+            /// UTC. Fails without a start, which carries the zone.
+            pub fn to_rrule(&self) -> Result<String, Error> {
+                write_zoned(self)
+            }
+        }
+    )*};
+}
+
+rrule!(OrderedWeekday, DayOfMonth, NthWeekday, DayOfYear);
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "chrono-tz")]
+    use crate::Zoned;
+    use crate::{
+        DayOfMonth, DayOfYear, Error, MissingDay, NthWeekday, OrderedWeekday, Recurrence,
+        Recurrent, RecurrentDay, Schedule,
+    };
+    use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    fn parse(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    /// Parses, checks the first occurrences and that formatting parses back to the same.
+    fn check<D: RecurrentDay>(rrule: &str, after: &str, expected: &[&str]) -> Recurrence<D>
+    where
+        Recurrence<D>: FromStr<Err = Error> + Display,
+    {
+        let recurrence: Recurrence<D> = rrule.parse().unwrap();
+        let occurrences: Vec<_> = recurrence
+            .occurrences_after(&parse(after))
+            .take(expected.len())
+            .collect();
+        let expected: Vec<_> = expected.iter().map(|e| parse(e)).collect();
+        assert_eq!(occurrences, expected);
+        assert_eq!(
+            recurrence.to_string().parse::<Recurrence<D>>().unwrap(),
+            recurrence
+        );
+        recurrence
+    }
+
+    /// Parses in the zone of `DTSTART`, checks the first occurrences and that formatting parses
+    /// back to the same.
+    #[cfg(feature = "chrono-tz")]
+    fn check_zoned<D: RecurrentDay>(
+        rrule: &str,
+        after: &str,
+        expected: &[&str],
+    ) -> Zoned<D, chrono_tz::Tz>
+    where
+        Zoned<D, chrono_tz::Tz>: FromStr<Err = Error>,
+    {
+        let zoned: Zoned<D, chrono_tz::Tz> = rrule.parse().unwrap();
+        let occurrences: Vec<_> = zoned
+            .occurrences_after(&parse(after))
+            .take(expected.len())
+            .collect();
+        let expected: Vec<_> = expected.iter().map(|e| parse(e)).collect();
+        assert_eq!(occurrences, expected);
+        zoned
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    #[test]
+    fn rfc_examples() {
+        // 09:00 in New York is 13:00 UTC in summer, 14:00 in winter
+        // Every other week on Monday, Wednesday, and Friday
+        let zoned = check_zoned::<OrderedWeekday>(
+            "DTSTART;TZID=America/New_York:19970901T090000\n\
+             RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE,FR",
+            "1997-09-01T00:00:00Z",
+            &[
+                "1997-09-01T13:00:00Z",
+                "1997-09-03T13:00:00Z",
+                "1997-09-05T13:00:00Z",
+                "1997-09-15T13:00:00Z",
+            ],
+        );
+        assert_eq!(
+            zoned.to_rrule().unwrap().parse::<Zoned<_, _>>().unwrap(),
+            zoned
+        );
+        // Weeks starting on Sunday change which ones every other week falls in
+        check_zoned::<OrderedWeekday>(
+            "DTSTART;TZID=America/New_York:19970805T090000\n\
+             RRULE:FREQ=WEEKLY;INTERVAL=2;COUNT=4;BYDAY=TU,SU;WKST=MO",
+            "1997-08-01T00:00:00Z",
+            &[
+                "1997-08-05T13:00:00Z",
+                "1997-08-10T13:00:00Z",
+                "1997-08-19T13:00:00Z",
+                "1997-08-24T13:00:00Z",
+            ],
+        );
+        let zoned = check_zoned::<OrderedWeekday>(
+            "DTSTART;TZID=America/New_York:19970805T090000\n\
+             RRULE:FREQ=WEEKLY;INTERVAL=2;COUNT=4;BYDAY=TU,SU;WKST=SU",
+            "1997-08-01T00:00:00Z",
+            &[
+                "1997-08-05T13:00:00Z",
+                "1997-08-17T13:00:00Z",
+                "1997-08-19T13:00:00Z",
+                "1997-08-31T13:00:00Z",
+            ],
+        );
+        assert_eq!(
+            zoned.to_rrule().unwrap().parse::<Zoned<_, _>>().unwrap(),
+            zoned
+        );
+        // Monthly on the third-to-the-last day of the month, forever
+        let zoned = check_zoned::<DayOfMonth>(
+            "DTSTART;TZID=America/New_York:19970928T090000\r\n\
+             RRULE:FREQ=MONTHLY;BYMONTHDAY=-3",
+            "1997-09-27T00:00:00Z",
+            &[
+                "1997-09-28T13:00:00Z",
+                "1997-10-29T14:00:00Z",
+                "1997-11-28T14:00:00Z",
+            ],
+        );
+        assert_eq!(
+            zoned.to_rrule().unwrap().parse::<Zoned<_, _>>().unwrap(),
+            zoned
+        );
+        // Monthly on the second-to-last Monday of the month
+        check_zoned::<NthWeekday>(
+            "DTSTART;TZID=America/New_York:19970922T090000\n\
+             RRULE:FREQ=MONTHLY;BYDAY=-2MO",
+            "1997-09-21T00:00:00Z",
+            &[
+                "1997-09-22T13:00:00Z",
+                "1997-10-20T13:00:00Z",
+                "1997-11-17T14:00:00Z",
+            ],
+        );
+        // Every Tuesday, every other month
+        check_zoned::<NthWeekday>(
+            "DTSTART;TZID=America/New_York:19970902T090000\n\
+             RRULE:FREQ=MONTHLY;INTERVAL=2;BYDAY=TU",
+            "1997-09-24T00:00:00Z",
+            &[
+                "1997-09-30T13:00:00Z",
+                "1997-11-04T14:00:00Z",
+                "1997-11-11T14:00:00Z",
+            ],
+        );
+        // Every Thursday in March, forever
+        let zoned = check_zoned::<DayOfYear>(
+            "DTSTART;TZID=America/New_York:19970313T090000\n\
+             RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=TH",
+            "1997-03-26T00:00:00Z",
+            &[
+                "1997-03-27T14:00:00Z",
+                "1998-03-05T14:00:00Z",
+                "1998-03-12T14:00:00Z",
+            ],
+        );
+        assert_eq!(
+            zoned.to_rrule().unwrap().parse::<Zoned<_, _>>().unwrap(),
+            zoned
+        );
+        // Yearly in June and July
+        check_zoned::<DayOfYear>(
+            "DTSTART;TZID=America/New_York:19970610T090000\n\
+             RRULE:FREQ=YEARLY;BYMONTH=6,7",
+            "1997-06-10T13:00:00Z",
+            &["1997-07-10T13:00:00Z", "1998-06-10T13:00:00Z"],
+        );
+        // Every 20 minutes from 9:00 AM to 4:40 PM every day
+        let daily = check_zoned::<OrderedWeekday>(
+            "DTSTART;TZID=America/New_York:19970902T090000\n\
+             RRULE:FREQ=DAILY;BYHOUR=9,10,11,12,13,14,15,16;BYMINUTE=0,20,40",
+            "1997-09-02T20:30:00Z",
+            &[
+                "1997-09-02T20:40:00Z",
+                "1997-09-03T13:00:00Z",
+                "1997-09-03T13:20:00Z",
+            ],
+        );
+        let minutely: Zoned<OrderedWeekday, _> = "DTSTART;TZID=America/New_York:19970902T090000\n\
+             RRULE:FREQ=MINUTELY;INTERVAL=20;BYHOUR=9,10,11,12,13,14,15,16"
+            .parse()
+            .unwrap();
+        assert_eq!(minutely, daily);
+        // Daily until December 24, 1997, in UTC
+        let zoned = check_zoned::<OrderedWeekday>(
+            "DTSTART;TZID=America/New_York:19970902T090000\n\
+             RRULE:FREQ=DAILY;UNTIL=19971224T000000Z",
+            "1997-12-22T15:00:00Z",
+            &["1997-12-23T14:00:00Z"],
+        );
+        assert_eq!(zoned.next(&parse("1997-12-23T14:00:00Z")), None);
+        assert_eq!(
+            zoned.to_rrule().unwrap(),
+            "DTSTART;TZID=America/New_York:19970902T090000\n\
+             RRULE:FREQ=WEEKLY;UNTIL=19971224T000000Z;BYDAY=MO,TU,WE,TH,FR,SA,SU;\
+             BYHOUR=9;BYMINUTE=0;BYSECOND=0"
+        );
+        assert_eq!(
+            zoned.to_rrule().unwrap().parse::<Zoned<_, _>>().unwrap(),
+            zoned
+        );
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    #[test]
+    fn zones() {
+        let paris: Zoned<OrderedWeekday, _> = "DTSTART;TZID=Europe/Paris:20210101T090000\n\
+             RRULE:FREQ=DAILY"
+            .parse()
+            .unwrap();
+        assert_eq!(paris.timezone(), &chrono_tz::Europe::Paris);
+        assert_eq!(
+            paris.next(&parse("2021-01-01T00:00:00Z")),
+            Some(parse("2021-01-01T08:00:00Z"))
+        );
+        let utc: Zoned<OrderedWeekday, _> = "DTSTART:20210101T090000Z\nRRULE:FREQ=DAILY"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            utc.next(&parse("2021-01-01T00:00:00Z")),
+            Some(parse("2021-01-01T09:00:00Z"))
+        );
+        assert_eq!(utc.to_rrule().unwrap().parse::<Zoned<_, _>>().unwrap(), utc);
+        assert!(matches!(
+            "FREQ=DAILY".parse::<Zoned<OrderedWeekday, chrono_tz::Tz>>(),
+            Err(Error::UnsupportedRRule(part)) if part == "DTSTART without TZID"
+        ));
+        assert!(matches!(
+            "DTSTART;TZID=Europe/Nowhere:20210101T090000\nRRULE:FREQ=DAILY"
+                .parse::<Zoned<OrderedWeekday, chrono_tz::Tz>>(),
+            Err(Error::UnknownTimeZone(_))
+        ));
+        // the zone needs a start to be written in
+        let recurrence = Recurrence::new(Weekday::Mon, NaiveTime::from_hms_opt(9, 0, 0).unwrap())
+            .unwrap()
+            .in_timezone(chrono_tz::Europe::Paris);
+        assert!(matches!(
+            recurrence.to_rrule(),
+            Err(Error::UnsupportedRRule(part)) if part == "TZID without DTSTART"
+        ));
+    }
+
+    #[test]
+    fn bounds() {
+        // Daily for 10 occurrences
+        let recurrence = check::<OrderedWeekday>(
+            "DTSTART:19970902T090000\n\
+             RRULE:FREQ=DAILY;COUNT=10",
+            "1997-01-01T00:00:00Z",
+            &["1997-09-02T09:00:00Z", "1997-09-03T09:00:00Z"],
+        );
+        assert_eq!(
+            recurrence
+                .occurrences_after(&parse("1997-01-01T00:00:00Z"))
+                .count(),
+            10
+        );
+        // Daily until December 24, 1997
+        let recurrence = check::<OrderedWeekday>(
+            "DTSTART:19970902T090000\n\
+             RRULE:FREQ=DAILY;UNTIL=19971224T000000",
+            "1997-12-22T12:00:00Z",
+            &["1997-12-23T09:00:00Z"],
+        );
+        assert_eq!(recurrence.next(&parse("1997-12-23T09:00:00Z")), None);
+        // a count over several lines is kept as the until it amounts to
+        let nine = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+        let half_past_five = NaiveTime::from_hms_opt(17, 30, 0).unwrap();
+        let recurrence = Recurrence::new(Weekday::Fri, (nine, half_past_five))
+            .unwrap()
+            .with_start(NaiveDate::from_ymd_opt(2020, 9, 4).unwrap().and_time(nine))
+            .with_count(3)
+            .unwrap();
+        assert_eq!(
+            recurrence.to_string(),
+            "DTSTART:20200904T090000\n\
+             RRULE:FREQ=WEEKLY;UNTIL=20200911T090000;BYDAY=FR;BYHOUR=9;BYMINUTE=0;BYSECOND=0\n\
+             RRULE:FREQ=WEEKLY;UNTIL=20200911T090000;BYDAY=FR;BYHOUR=17;BYMINUTE=30;BYSECOND=0"
+        );
+    }
+
+    #[test]
+    fn schedule() {
+        let schedule: Schedule<OrderedWeekday> = "DTSTART:20200903T090000\n\
+             RRULE:FREQ=WEEKLY;BYDAY=MO,TH;BYHOUR=9;BYMINUTE=0;BYSECOND=0\n\
+             EXDATE;VALUE=DATE:20200907\n\
+             EXDATE:20200910T090000\n\
+             RDATE:20200912T090000"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            schedule
+                .occurrences_after(&parse("2020-09-03T09:00:00Z"))
+                .next(),
+            Some(parse("2020-09-12T09:00:00Z"))
+        );
+        assert_eq!(
+            schedule.to_string().parse::<Schedule<_>>().unwrap(),
+            schedule
+        );
+
+        // moved occurrences lose their identity
+        let mut moved = schedule.clone();
+        let at = |day: u32| {
+            NaiveDate::from_ymd_opt(2020, 9, day)
+                .unwrap()
+                .and_hms_opt(9, 0, 0)
+                .unwrap()
+        };
+        moved.reschedule(at(14), at(15)).unwrap();
+        assert_eq!(
+            moved.to_string(),
+            "DTSTART:20200903T090000\n\
+             RRULE:FREQ=WEEKLY;BYDAY=MO,TH;BYHOUR=9;BYMINUTE=0;BYSECOND=0\n\
+             EXDATE;VALUE=DATE:20200907\n\
+             EXDATE:20200910T090000,20200914T090000\n\
+             RDATE:20200912T090000,20200915T090000"
+        );
+        assert!(matches!(moved.to_rrule(), Err(Error::UnsupportedRRule(_))));
+        assert_eq!(schedule.to_rrule().unwrap(), schedule.to_string());
+
+        let schedule: Schedule<OrderedWeekday> =
+            "FREQ=WEEKLY;BYDAY=MO;BYHOUR=9;BYMINUTE=0;BYSECOND=0"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            schedule.to_string(),
+            "FREQ=WEEKLY;BYDAY=MO;BYHOUR=9;BYMINUTE=0;BYSECOND=0"
+        );
+    }
+
+    #[test]
+    fn format() {
+        let nine = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+        let half_past_five = NaiveTime::from_hms_opt(17, 30, 0).unwrap();
+        let recurrence = Recurrence::new((Weekday::Mon, Weekday::Wed), nine).unwrap();
+        assert_eq!(
+            recurrence.to_string(),
+            "FREQ=WEEKLY;BYDAY=MO,WE;BYHOUR=9;BYMINUTE=0;BYSECOND=0"
+        );
+        assert_eq!(
+            "FREQ=WEEKLY;BYDAY=MO,WE;BYHOUR=9"
+                .parse::<Recurrence<_>>()
+                .unwrap(),
+            recurrence
+        );
+        let recurrence = Recurrence::new(Weekday::Fri, (nine, half_past_five))
+            .unwrap()
+            .with_interval(2, NaiveDate::from_ymd_opt(2020, 9, 4).unwrap())
+            .unwrap()
+            .with_start(NaiveDate::from_ymd_opt(2020, 9, 4).unwrap().and_time(nine));
+        assert_eq!(
+            recurrence.to_string(),
+            "DTSTART:20200904T090000\n\
+             RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=FR;BYHOUR=9;BYMINUTE=0;BYSECOND=0\n\
+             RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=FR;BYHOUR=17;BYMINUTE=30;BYSECOND=0"
+        );
+        assert_eq!(
+            recurrence.to_string().parse::<Recurrence<_>>().unwrap(),
+            recurrence
+        );
+
+        // RRULEs anchor intervals on their start, the one they parse back with
+        let sprint = Recurrence::new(Weekday::Mon, nine)
+            .unwrap()
+            .with_interval(2, NaiveDate::from_ymd_opt(2020, 8, 31).unwrap())
+            .unwrap();
+        assert!(matches!(
+            sprint.to_rrule(),
+            Err(Error::UnsupportedRRule(part)) if part == "INTERVAL without DTSTART"
+        ));
+        assert_eq!(
+            sprint.to_string(),
+            "DTSTART:20200831T090000\n\
+             RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO;BYHOUR=9;BYMINUTE=0;BYSECOND=0"
+        );
+        let parsed: Recurrence<OrderedWeekday> = sprint.to_string().parse().unwrap();
+        let monday = parse("2020-08-31T09:00:00Z");
+        assert_eq!(sprint.prev(&monday), Some(parse("2020-08-17T09:00:00Z")));
+        assert_eq!(parsed.prev(&monday), None);
+        assert_eq!(parsed.next(&monday), sprint.next(&monday));
+
+        let recurrence = Recurrence::monthly(&[31], nine, MissingDay::Clamp).unwrap();
+        assert_eq!(
+            recurrence.to_string(),
+            "FREQ=MONTHLY;BYMONTHDAY=31;RSCALE=GREGORIAN;SKIP=BACKWARD;BYHOUR=9;BYMINUTE=0;BYSECOND=0"
+        );
+        assert_eq!(
+            recurrence.to_string().parse::<Recurrence<_>>().unwrap(),
+            recurrence
+        );
+
+        let thanksgiving = DayOfYear::nth_weekday(11, 4, Weekday::Thu).unwrap();
+        let christmas = DayOfYear::date(12, 25, MissingDay::Skip).unwrap();
+        let recurrence = Recurrence::yearly(&[thanksgiving, christmas], nine).unwrap();
+        assert_eq!(
+            recurrence.to_string(),
+            "RRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=4TH;BYHOUR=9;BYMINUTE=0;BYSECOND=0\n\
+             RRULE:FREQ=YEARLY;BYMONTH=12;BYMONTHDAY=25;BYHOUR=9;BYMINUTE=0;BYSECOND=0"
+        );
+        assert_eq!(
+            recurrence.to_string().parse::<Recurrence<_>>().unwrap(),
+            recurrence
+        );
+
+        let recurrence = Recurrence::every(
+            Weekday::Sat,
+            nine,
+            NaiveTime::from_hms_opt(10, 0, 0).unwrap(),
+            chrono::Duration::minutes(30),
+        )
+        .unwrap();
+        let parsed: Recurrence<OrderedWeekday> = recurrence.to_string().parse().unwrap();
+        let saturday = Utc.with_ymd_and_hms(2020, 9, 5, 0, 0, 0).unwrap();
+        assert_eq!(
+            parsed
+                .occurrences_after(&saturday)
+                .take(4)
+                .collect::<Vec<_>>(),
+            recurrence
+                .occurrences_after(&saturday)
+                .take(4)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn errors() {
+        fn error<D: RecurrentDay>(rrule: &str) -> Error
+        where
+            Recurrence<D>: FromStr<Err = Error>,
+        {
+            rrule.parse::<Recurrence<D>>().unwrap_err()
+        }
+        assert!(matches!(
+            error::<OrderedWeekday>("FREQ=DAILY;BYSETPOS=1"),
+            Error::UnsupportedRRule(part) if part == "BYSETPOS=1"
+        ));
+        assert!(matches!(
+            error::<OrderedWeekday>("FREQ=DAILY;COUNT=10"),
+            Error::InvalidRRule(part) if part == "COUNT without DTSTART"
+        ));
+        assert!(matches!(
+            error::<NthWeekday>("FREQ=MONTHLY;BYDAY=FR;BYMONTHDAY=13"),
+            Error::UnsupportedRRule(part) if part == "BYMONTHDAY"
+        ));
+        assert!(matches!(
+            error::<OrderedWeekday>("FREQ=MONTHLY;BYMONTHDAY=1"),
+            Error::UnsupportedRRule(part) if part == "FREQ=MONTHLY"
+        ));
+        assert!(matches!(
+            error::<OrderedWeekday>("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO"),
+            Error::UnsupportedRRule(_)
+        ));
+        assert!(matches!(
+            error::<OrderedWeekday>("FREQ=WEEKLY;BYDAY=XX"),
+            Error::InvalidRRule(part) if part == "BYDAY=XX"
+        ));
+        assert!(matches!(
+            error::<OrderedWeekday>("FREQ=WEEKLY;BYDAY=ÖA"),
+            Error::InvalidRRule(part) if part == "BYDAY=ÖA"
+        ));
+        assert!(matches!(
+            error::<OrderedWeekday>("FREQ=DAILY;INTERVAL=2,3"),
+            Error::InvalidRRule(part) if part == "INTERVAL=2,3"
+        ));
+        assert!(matches!(
+            error::<OrderedWeekday>("FREQ=DAILY;COUNT=5,6"),
+            Error::InvalidRRule(part) if part == "COUNT=5,6"
+        ));
+        // zones are not dropped: a recurrence has floating wall times only
+        assert!(matches!(
+            error::<OrderedWeekday>("DTSTART;TZID=Europe/Paris:20210101T090000\nRRULE:FREQ=DAILY"),
+            Error::UnsupportedRRule(part) if part == "DTSTART in Europe/Paris"
+        ));
+        assert!(matches!(
+            error::<OrderedWeekday>("DTSTART:20210101T090000Z\nRRULE:FREQ=DAILY"),
+            Error::UnsupportedRRule(part) if part == "DTSTART in UTC"
+        ));
+        assert!(matches!(
+            error::<OrderedWeekday>("FREQ=DAILY;UNTIL=19971224T000000Z"),
+            Error::UnsupportedRRule(part) if part == "UNTIL=19971224T000000Z"
+        ));
+        assert!(matches!(
+            "RRULE:FREQ=DAILY\nEXDATE;TZID=Europe/Paris:20210101T090000"
+                .parse::<Schedule<OrderedWeekday>>(),
+            Err(Error::UnsupportedRRule(_))
+        ));
+        assert!(matches!(
+            error::<OrderedWeekday>("FREQ=FORTNIGHTLY"),
+            Error::InvalidRRule(_)
+        ));
+        assert!(matches!(
+            error::<DayOfMonth>("FREQ=MONTHLY;BYMONTHDAY=0"),
+            Error::InvalidRRule(_)
+        ));
+        assert!(matches!(
+            error::<OrderedWeekday>(
+                "RRULE:FREQ=WEEKLY;BYDAY=MO;BYHOUR=9\nRRULE:FREQ=WEEKLY;BYDAY=TU;BYHOUR=10"
+            ),
+            Error::UnsupportedRRule(_)
+        ));
+    }
+}
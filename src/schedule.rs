@@ -0,0 +1,363 @@
+use crate::{dst, Error, Fold, Gap, Recurrence, Recurrent, RecurrentDay};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A [`Recurrence`] with occurrences cancelled, added or moved one by one, like the `EXDATE`,
+/// `RDATE` and `RECURRENCE-ID` of RFC 5545.
+///
+/// Excluded, added and moved occurrences are wall times, like the ones of the recurrence.
+/// Exclusions apply to added occurrences too, and added occurrences are not subject to the bounds
+/// of the recurrence. A moved occurrence keeps the identity of the one it replaces, see
+/// [`Occurrence`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Schedule<D: RecurrentDay> {
+    recurrence: Recurrence<D>,
+    excluded: BTreeSet<NaiveDateTime>,
+    excluded_dates: BTreeSet<NaiveDate>,
+    added: BTreeSet<NaiveDateTime>,
+    rescheduled: BTreeMap<NaiveDateTime, NaiveDateTime>,
+}
+
+/// An occurrence of a [`Schedule`], along with the one it stands for when it has been moved.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Occurrence<T: TimeZone> {
+    at: DateTime<T>,
+    original: DateTime<T>,
+}
+
+impl<T: TimeZone> Occurrence<T> {
+    fn new(at: DateTime<T>) -> Self {
+        Occurrence {
+            original: at.clone(),
+            at,
+        }
+    }
+
+    /// When it occurs.
+    pub fn at(&self) -> &DateTime<T> {
+        &self.at
+    }
+
+    /// When it would have occurred without being moved, identifying it within its schedule.
+    pub fn original(&self) -> &DateTime<T> {
+        &self.original
+    }
+
+    pub fn is_moved(&self) -> bool {
+        self.at != self.original
+    }
+}
+
+impl<D: RecurrentDay> Schedule<D> {
+    pub fn new(recurrence: Recurrence<D>) -> Self {
+        Schedule {
+            recurrence,
+            excluded: BTreeSet::new(),
+            excluded_dates: BTreeSet::new(),
+            added: BTreeSet::new(),
+            rescheduled: BTreeMap::new(),
+        }
+    }
+
+    pub fn recurrence(&self) -> &Recurrence<D> {
+        &self.recurrence
+    }
+
+    /// Cancels the occurrence at the `occurrence` wall time.
+    pub fn exclude(&mut self, occurrence: NaiveDateTime) {
+        self.excluded.insert(occurrence);
+    }
+
+    /// Cancels all the occurrences on `date`.
+    pub fn exclude_date(&mut self, date: NaiveDate) {
+        self.excluded_dates.insert(date);
+    }
+
+    /// Adds a one-off occurrence at the `occurrence` wall time.
+    pub fn add(&mut self, occurrence: NaiveDateTime) {
+        self.added.insert(occurrence);
+    }
+
+    /// Moves the occurrence at the `original` wall time, from the recurrence within its bounds or
+    /// added, to the `moved` one. Excluding the original occurrence cancels it wherever it has
+    /// been moved.
+    pub fn reschedule(
+        &mut self,
+        original: NaiveDateTime,
+        moved: NaiveDateTime,
+    ) -> Result<(), Error> {
+        let recurring = self
+            .recurrence
+            .next_local(original - Duration::nanoseconds(1))
+            == Some(original)
+            && self.recurrence.start.is_none_or(|start| original >= start)
+            && self.recurrence.end().is_none_or(|end| original <= end);
+        if !recurring && !self.added.contains(&original) {
+            return Err(Error::NoOccurrence(original));
+        }
+        self.rescheduled.insert(original, moved);
+        Ok(())
+    }
+
+    pub fn excluded(&self) -> &BTreeSet<NaiveDateTime> {
+        &self.excluded
+    }
+
+    pub fn excluded_dates(&self) -> &BTreeSet<NaiveDate> {
+        &self.excluded_dates
+    }
+
+    pub fn added(&self) -> &BTreeSet<NaiveDateTime> {
+        &self.added
+    }
+
+    /// The moved occurrences, from their original wall time to their new one.
+    pub fn rescheduled(&self) -> &BTreeMap<NaiveDateTime, NaiveDateTime> {
+        &self.rescheduled
+    }
+
+    fn is_excluded(&self, local: NaiveDateTime) -> bool {
+        self.excluded.contains(&local) || self.excluded_dates.contains(&local.date())
+    }
+
+    /// Whether the occurrence at `local` doesn't happen there.
+    fn is_cancelled(&self, local: NaiveDateTime) -> bool {
+        self.is_excluded(local) || self.rescheduled.contains_key(&local)
+    }
+
+    fn moved<'a, T: TimeZone>(&'a self, tz: &'a T) -> impl Iterator<Item = Occurrence<T>> + 'a {
+        self.rescheduled
+            .iter()
+            .filter(move |(original, _)| !self.is_excluded(**original))
+            .filter_map(move |(original, moved)| {
+                let at = self.resolve(tz, *moved)?;
+                let original = self.resolve(tz, *original).unwrap_or_else(|| at.clone());
+                Some(Occurrence { at, original })
+            })
+    }
+
+    fn resolve<T: TimeZone>(&self, tz: &T, local: NaiveDateTime) -> Option<DateTime<T>> {
+        dst::resolve(tz, local, self.recurrence.gap, self.recurrence.fold)
+    }
+
+    /// First occurrence strictly after `date`, along with its original identity.
+    pub fn next_occurrence<T: TimeZone>(&self, date: &DateTime<T>) -> Option<Occurrence<T>> {
+        let mut cursor = date.clone();
+        let recurring = loop {
+            match self.recurrence.next(&cursor) {
+                Some(next) if self.is_cancelled(next.naive_local()) => cursor = next,
+                next => break next,
+            }
+        };
+        let tz = date.timezone();
+        let added = self
+            .added
+            .range(date.naive_local() - dst::slack_after(date)..)
+            .filter(|local| !self.is_cancelled(**local))
+            .filter_map(|local| self.resolve(&tz, *local))
+            .find(|added| added > date);
+        let moved = self
+            .moved(&tz)
+            .filter(|moved| moved.at > *date)
+            .min_by(|a, b| a.at.cmp(&b.at));
+        recurring
+            .into_iter()
+            .chain(added)
+            .map(Occurrence::new)
+            .chain(moved)
+            .min_by(|a, b| a.at.cmp(&b.at))
+    }
+
+    /// Last occurrence strictly before `date`, along with its original identity.
+    pub fn prev_occurrence<T: TimeZone>(&self, date: &DateTime<T>) -> Option<Occurrence<T>> {
+        let mut cursor = date.clone();
+        let recurring = loop {
+            match self.recurrence.prev(&cursor) {
+                Some(prev) if self.is_cancelled(prev.naive_local()) => cursor = prev,
+                prev => break prev,
+            }
+        };
+        let tz = date.timezone();
+        let added = self
+            .added
+            .range(..=date.naive_local() + dst::slack_before(date))
+            .rev()
+            .filter(|local| !self.is_cancelled(**local))
+            .filter_map(|local| self.resolve(&tz, *local))
+            .find(|added| added < date);
+        let moved = self
+            .moved(&tz)
+            .filter(|moved| moved.at < *date)
+            .max_by(|a, b| a.at.cmp(&b.at));
+        recurring
+            .into_iter()
+            .chain(added)
+            .map(Occurrence::new)
+            .chain(moved)
+            .max_by(|a, b| a.at.cmp(&b.at))
+    }
+}
+
+impl<D: RecurrentDay> Recurrent for Schedule<D> {
+    fn next<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>> {
+        self.next_occurrence(date).map(|occurrence| occurrence.at)
+    }
+
+    fn prev<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>> {
+        self.prev_occurrence(date).map(|occurrence| occurrence.at)
+    }
+
+    fn dst_policy(&self) -> (Gap, Fold) {
+        self.recurrence.dst_policy()
+    }
+}
+
+impl<D: RecurrentDay> From<Recurrence<D>> for Schedule<D> {
+    fn from(recurrence: Recurrence<D>) -> Self {
+        Schedule::new(recurrence)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, OrderedWeekday, Recurrence, Recurrent, Schedule};
+    use chrono::{DateTime, NaiveDate, NaiveTime, Utc, Weekday};
+
+    fn parse(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn standup() -> Schedule<OrderedWeekday> {
+        let nine = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+        let mut schedule: Schedule<_> = Recurrence::new((Weekday::Mon, Weekday::Thu), nine)
+            .unwrap()
+            .into();
+        // labour day
+        schedule.exclude_date(NaiveDate::from_ymd_opt(2020, 9, 7).unwrap());
+        schedule.exclude(NaiveDate::from_ymd_opt(2020, 9, 10).unwrap().and_time(nine));
+        // catching up on saturday
+        schedule.add(NaiveDate::from_ymd_opt(2020, 9, 12).unwrap().and_time(nine));
+        schedule
+    }
+
+    #[test]
+    fn schedule() {
+        let schedule = standup();
+        assert_eq!(
+            schedule
+                .occurrences_after(&parse("2020-09-03T09:00:00Z"))
+                .take(3)
+                .collect::<Vec<_>>(),
+            vec![
+                parse("2020-09-12T09:00:00Z"),
+                parse("2020-09-14T09:00:00Z"),
+                parse("2020-09-17T09:00:00Z"),
+            ]
+        );
+        assert_eq!(
+            schedule
+                .occurrences_before(&parse("2020-09-14T09:00:00Z"))
+                .take(2)
+                .collect::<Vec<_>>(),
+            vec![parse("2020-09-12T09:00:00Z"), parse("2020-09-03T09:00:00Z")]
+        );
+        assert_eq!(
+            schedule
+                .between(
+                    &parse("2020-09-03T09:00:00Z"),
+                    &parse("2020-09-14T09:00:00Z"),
+                    true
+                )
+                .count(),
+            3
+        );
+        assert_eq!(
+            schedule.next(&parse("2020-09-12T08:00:00Z")),
+            Some(parse("2020-09-12T09:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn added_only() {
+        let noon = NaiveDate::from_ymd_opt(2020, 9, 1)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let recurrence = Recurrence::new(Weekday::Mon, noon.time())
+            .unwrap()
+            .with_until(noon);
+        let mut schedule = Schedule::new(recurrence);
+        assert_eq!(schedule.next(&parse("2020-09-01T00:00:00Z")), None);
+        schedule.add(noon);
+        assert_eq!(
+            schedule.next(&parse("2020-09-01T00:00:00Z")),
+            Some(parse("2020-09-01T12:00:00Z"))
+        );
+        assert_eq!(schedule.next(&parse("2020-09-01T12:00:00Z")), None);
+    }
+
+    #[test]
+    fn rescheduled() {
+        let at = |day: u32, hour: u32| {
+            NaiveDate::from_ymd_opt(2020, 9, day)
+                .unwrap()
+                .and_hms_opt(hour, 0, 0)
+                .unwrap()
+        };
+        let mut schedule = Schedule::new(
+            Recurrence::new(Weekday::Wed, NaiveTime::from_hms_opt(10, 0, 0).unwrap()).unwrap(),
+        );
+        // from wednesday 10:00 to thursday 14:00, just once
+        schedule.reschedule(at(9, 10), at(10, 14)).unwrap();
+        let moved = schedule
+            .next_occurrence(&parse("2020-09-02T10:00:00Z"))
+            .unwrap();
+        assert_eq!(moved.at(), &parse("2020-09-10T14:00:00Z"));
+        assert_eq!(moved.original(), &parse("2020-09-09T10:00:00Z"));
+        assert!(moved.is_moved());
+        assert_eq!(
+            schedule
+                .occurrences_after(&parse("2020-09-02T10:00:00Z"))
+                .take(2)
+                .collect::<Vec<_>>(),
+            vec![parse("2020-09-10T14:00:00Z"), parse("2020-09-16T10:00:00Z")]
+        );
+        let before = schedule
+            .prev_occurrence(&parse("2020-09-16T10:00:00Z"))
+            .unwrap();
+        assert_eq!(before, moved);
+        assert!(!schedule
+            .prev_occurrence(&parse("2020-09-10T14:00:00Z"))
+            .unwrap()
+            .is_moved());
+
+        // cancelling the original cancels the moved one
+        schedule.exclude(at(9, 10));
+        assert_eq!(
+            schedule.next(&parse("2020-09-02T10:00:00Z")),
+            Some(parse("2020-09-16T10:00:00Z"))
+        );
+
+        assert!(matches!(
+            schedule.reschedule(at(9, 11), at(10, 14)),
+            Err(Error::NoOccurrence(_))
+        ));
+        // out of the bounds of the recurrence
+        let mut schedule = Schedule::new(
+            Recurrence::new(Weekday::Wed, NaiveTime::from_hms_opt(10, 0, 0).unwrap())
+                .unwrap()
+                .with_start(at(9, 10))
+                .with_count(2)
+                .unwrap(),
+        );
+        assert!(matches!(
+            schedule.reschedule(at(2, 10), at(3, 14)),
+            Err(Error::NoOccurrence(_))
+        ));
+        assert!(matches!(
+            schedule.reschedule(at(23, 10), at(24, 14)),
+            Err(Error::NoOccurrence(_))
+        ));
+        schedule.reschedule(at(16, 10), at(17, 14)).unwrap();
+    }
+}
@@ -0,0 +1,1090 @@
+//! Serde support, as human-friendly maps: `{"days": ["Mon", "Fri"], "time": "09:00"}`.
+//!
+//! Times of day are `HH:MM` or `HH:MM:SS`, dates `YYYY-MM-DD` and wall times
+//! `YYYY-MM-DDTHH:MM:SS`. Deserializing validates like the constructors do.
+//!
+//! A [`Cron`] is its expression, a [`Zoned`](crate::Zoned) recurrence names its zone (with the
+//! `chrono-tz` feature) and combinations wrap what they combine: `{"union": [..., ...]}`.
+
+use crate::holiday::Holiday;
+use crate::times::DayTimes;
+use crate::yearly::YearlyDay;
+#[cfg(feature = "chrono-tz")]
+use crate::Zoned;
+use crate::{
+    Adjusted, Adjustment, BusinessDays, Cron, DayOfMonth, DayOfYear, Difference, Error, Event,
+    Fold, Gap, HolidayCalendar, Holidays, Intersection, MissingDay, NthWeekday, OrderedWeekday,
+    Recurrence, Recurrent, RecurrentDay, Schedule, StateMachine, Union, WeekStart,
+};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use serde::de::{self, Deserializer};
+use serde::ser::{self, Serializer};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Serializes as a string, with `format`, and deserializes from a string, with `parse`.
+macro_rules! string {
+    ($name:ident($inner:ty), $format:expr, $parse:expr) => {
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+        struct $name($inner);
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let format: fn(&$inner) -> String = $format;
+                serializer.serialize_str(&format(&self.0))
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let s = String::deserialize(deserializer)?;
+                let parse: fn(&str) -> Option<$inner> = $parse;
+                parse(&s).map($name).ok_or_else(|| {
+                    de::Error::custom(format!("invalid {}: {}", stringify!($name), s))
+                })
+            }
+        }
+    };
+}
+
+string!(
+    Time(NaiveTime),
+    |time| match (time.second(), time.nanosecond()) {
+        (0, 0) => time.format("%H:%M").to_string(),
+        _ => time.format("%H:%M:%S%.f").to_string(),
+    },
+    |s| {
+        NaiveTime::parse_from_str(s, "%H:%M:%S%.f")
+            .or_else(|_| NaiveTime::parse_from_str(s, "%H:%M"))
+            .ok()
+    }
+);
+
+string!(Date(NaiveDate), |date| date.to_string(), |s| s.parse().ok());
+
+string!(
+    WallTime(NaiveDateTime),
+    |date| date.format("%Y-%m-%dT%H:%M:%S%.f").to_string(),
+    |s| s.parse().ok()
+);
+
+// e.g. `15m`
+string!(
+    Step(Duration),
+    |step| {
+        let seconds = step.num_seconds();
+        if seconds % 3600 == 0 {
+            format!("{}h", seconds / 3600)
+        } else if seconds % 60 == 0 {
+            format!("{}m", seconds / 60)
+        } else {
+            format!("{}s", seconds)
+        }
+    },
+    |s| {
+        let unit = match s.chars().last()? {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        let count: i64 = s[..s.len() - 1].parse().ok()?;
+        Some(Duration::seconds(count.checked_mul(unit)?))
+    }
+);
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct RawEvery {
+    from: Time,
+    to: Time,
+    step: Step,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawInterval {
+    every: u32,
+    anchor: Date,
+}
+
+fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+/// `"time": "09:00"`, `"times": ["09:00", "17:00"]` or `"every": {...}`.
+#[derive(Serialize, Deserialize)]
+struct RawTimes {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    time: Option<Time>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    times: Vec<Time>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    every: Option<RawEvery>,
+}
+
+impl RawTimes {
+    fn new(times: &DayTimes) -> Self {
+        match times {
+            DayTimes::List(list) if list.len() == 1 => RawTimes {
+                time: Some(Time(times.first())),
+                times: vec![],
+                every: None,
+            },
+            DayTimes::List(list) => RawTimes {
+                time: None,
+                times: list.iter().map(|t| Time(*t)).collect(),
+                every: None,
+            },
+            DayTimes::Every { from, to, step } => RawTimes {
+                time: None,
+                times: vec![],
+                every: Some(RawEvery {
+                    from: Time(*from),
+                    to: Time(*to),
+                    step: Step(Duration::seconds(*step)),
+                }),
+            },
+        }
+    }
+
+    /// The times for the constructors to validate, the start of `every` standing for it.
+    fn list<E: de::Error>(&self) -> Result<BTreeSet<NaiveTime>, E> {
+        match self.every {
+            Some(_) if self.time.is_some() || !self.times.is_empty() => {
+                Err(E::custom("`every` excludes `time` and `times`"))
+            }
+            Some(every) => Ok(std::iter::once(every.from.0).collect()),
+            None => Ok(self.time.iter().chain(&self.times).map(|t| t.0).collect()),
+        }
+    }
+
+    /// The times of `every`, replacing the ones validated.
+    fn every(&self) -> Result<Option<DayTimes>, Error> {
+        self.every
+            .map(|every| DayTimes::every(every.from.0, every.to.0, every.step.0))
+            .transpose()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawRecurrence<D> {
+    days: Vec<D>,
+    #[serde(flatten)]
+    times: RawTimes,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    interval: Option<RawInterval>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start: Option<WallTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    until: Option<WallTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    count: Option<u32>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    gap: Gap,
+    #[serde(default, skip_serializing_if = "is_default")]
+    fold: Fold,
+    #[serde(default, skip_serializing_if = "is_default")]
+    week_start: WeekStart,
+}
+
+impl<D: RecurrentDay> RawRecurrence<D> {
+    fn build(self, times: BTreeSet<NaiveTime>) -> Result<Recurrence<D>, Error> {
+        let days: BTreeSet<D> = self.days.into_iter().collect();
+        let mut recurrence = Recurrence::from_days(days, times)?;
+        if let Some(every) = self.times.every()? {
+            recurrence.times = every;
+        }
+        if let Some(interval) = self.interval {
+            recurrence = recurrence.with_interval(interval.every, interval.anchor.0)?;
+        }
+        if let Some(start) = self.start {
+            recurrence = recurrence.with_start(start.0);
+        }
+        if let Some(until) = self.until {
+            recurrence = recurrence.with_until(until.0);
+        }
+        if let Some(count) = self.count {
+            recurrence = recurrence.with_count(count)?;
+        }
+        Ok(recurrence
+            .with_gap(self.gap)
+            .with_fold(self.fold)
+            .with_week_start(self.week_start))
+    }
+}
+
+impl<D: RecurrentDay + Serialize> Serialize for Recurrence<D> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RawRecurrence {
+            days: self.days.iter().copied().collect(),
+            times: RawTimes::new(&self.times),
+            interval: self.interval.map(|interval| RawInterval {
+                every: interval.every,
+                anchor: Date(interval.anchor),
+            }),
+            start: self.start.map(WallTime),
+            until: self.until.map(WallTime),
+            count: self.count,
+            gap: self.gap,
+            fold: self.fold,
+            week_start: self.week_start,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Validates through the constructors, e.g. an empty day list fails with [`Error::Empty`].
+impl<'de, D: RecurrentDay + Deserialize<'de>> Deserialize<'de> for Recurrence<D> {
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        let raw = RawRecurrence::<D>::deserialize(deserializer)?;
+        let times = raw.times.list()?;
+        raw.build(times).map_err(de::Error::custom)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawSchedule<R> {
+    #[serde(flatten)]
+    recurrence: R,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    exclude: Vec<WallTime>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    exclude_dates: Vec<Date>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    add: Vec<WallTime>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    reschedule: BTreeMap<WallTime, WallTime>,
+}
+
+impl<D: RecurrentDay + Serialize> Serialize for Schedule<D> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RawSchedule {
+            recurrence: self.recurrence(),
+            exclude: self.excluded().iter().copied().map(WallTime).collect(),
+            exclude_dates: self.excluded_dates().iter().copied().map(Date).collect(),
+            add: self.added().iter().copied().map(WallTime).collect(),
+            reschedule: self
+                .rescheduled()
+                .iter()
+                .map(|(original, moved)| (WallTime(*original), WallTime(*moved)))
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, D: RecurrentDay + Deserialize<'de>> Deserialize<'de> for Schedule<D> {
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        let raw = RawSchedule::<Recurrence<D>>::deserialize(deserializer)?;
+        let mut schedule = Schedule::new(raw.recurrence);
+        raw.exclude.iter().for_each(|date| schedule.exclude(date.0));
+        raw.exclude_dates
+            .iter()
+            .for_each(|date| schedule.exclude_date(date.0));
+        raw.add.iter().for_each(|date| schedule.add(date.0));
+        for (original, moved) in raw.reschedule {
+            schedule
+                .reschedule(original.0, moved.0)
+                .map_err(de::Error::custom)?;
+        }
+        Ok(schedule)
+    }
+}
+
+fn is_skip(missing: &MissingDay) -> bool {
+    *missing == MissingDay::Skip
+}
+
+fn skip() -> MissingDay {
+    MissingDay::Skip
+}
+
+/// `31`, or `{"day": 31, "missing": "clamp"}`.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum RawDayOfMonth {
+    Day(i8),
+    Missing { day: i8, missing: MissingDay },
+}
+
+impl Serialize for DayOfMonth {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.missing() {
+            MissingDay::Skip => RawDayOfMonth::Day(self.day()),
+            missing => RawDayOfMonth::Missing {
+                day: self.day(),
+                missing,
+            },
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DayOfMonth {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (day, missing) = match RawDayOfMonth::deserialize(deserializer)? {
+            RawDayOfMonth::Day(day) => (day, MissingDay::Skip),
+            RawDayOfMonth::Missing { day, missing } => (day, missing),
+        };
+        DayOfMonth::new(day, missing).map_err(de::Error::custom)
+    }
+}
+
+/// `{"nth": -1, "weekday": "Fri"}`.
+#[derive(Serialize, Deserialize)]
+struct RawNthWeekday {
+    nth: i8,
+    weekday: OrderedWeekday,
+}
+
+impl Serialize for NthWeekday {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RawNthWeekday {
+            nth: self.nth(),
+            weekday: self.weekday(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for NthWeekday {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawNthWeekday::deserialize(deserializer)?;
+        NthWeekday::new(raw.nth, raw.weekday.into()).map_err(de::Error::custom)
+    }
+}
+
+/// `{"month": 12, "day": 25}`, or `{"month": 11, "nth": 4, "weekday": "Thu"}`.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum RawDayOfYear {
+    Date {
+        month: u32,
+        day: u32,
+        #[serde(default = "skip", skip_serializing_if = "is_skip")]
+        missing: MissingDay,
+    },
+    Nth {
+        month: u32,
+        nth: i8,
+        weekday: OrderedWeekday,
+    },
+}
+
+impl Serialize for DayOfYear {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let month = self.month();
+        match self.day() {
+            YearlyDay::Date(day) => RawDayOfYear::Date {
+                month,
+                day: day.day() as u32,
+                missing: day.missing(),
+            },
+            YearlyDay::Nth(nth) => RawDayOfYear::Nth {
+                month,
+                nth: nth.nth(),
+                weekday: nth.weekday(),
+            },
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DayOfYear {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match RawDayOfYear::deserialize(deserializer)? {
+            RawDayOfYear::Date {
+                month,
+                day,
+                missing,
+            } => DayOfYear::date(month, day, missing),
+            RawDayOfYear::Nth {
+                month,
+                nth,
+                weekday,
+            } => DayOfYear::nth_weekday(month, nth, weekday.into()),
+        }
+        .map_err(de::Error::custom)
+    }
+}
+
+/// Named after its variant, along with its fields: `"empty"`, `{"invalid_nth": 24}`...
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum RawError {
+    Empty,
+    NoTime,
+    InvalidDayOfMonth(i8),
+    InvalidNth(i8),
+    InvalidMonth(u32),
+    InvalidInterval,
+    InvalidWindow,
+    InvalidStep,
+    #[serde(rename = "invalid_rrule")]
+    InvalidRRule(String),
+    #[serde(rename = "unsupported_rrule")]
+    UnsupportedRRule(String),
+    InvalidCron(String),
+    UnsupportedCron(String),
+    UnknownTimeZone(String),
+    InvalidCount,
+    NoStart,
+    NoOccurrence(WallTime),
+    InvalidDuration,
+    SearchLimit,
+    UnexpectedToken {
+        position: usize,
+        expected: String,
+        found: String,
+    },
+    UnexpectedEnd {
+        position: usize,
+        expected: String,
+    },
+}
+
+impl Serialize for Error {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.clone() {
+            Error::Empty => RawError::Empty,
+            Error::NoTime => RawError::NoTime,
+            Error::InvalidDayOfMonth(day) => RawError::InvalidDayOfMonth(day),
+            Error::InvalidNth(nth) => RawError::InvalidNth(nth),
+            Error::InvalidMonth(month) => RawError::InvalidMonth(month),
+            Error::InvalidInterval => RawError::InvalidInterval,
+            Error::InvalidWindow => RawError::InvalidWindow,
+            Error::InvalidStep => RawError::InvalidStep,
+            Error::InvalidRRule(part) => RawError::InvalidRRule(part),
+            Error::UnsupportedRRule(part) => RawError::UnsupportedRRule(part),
+            Error::InvalidCron(part) => RawError::InvalidCron(part),
+            Error::UnsupportedCron(part) => RawError::UnsupportedCron(part),
+            Error::UnknownTimeZone(name) => RawError::UnknownTimeZone(name),
+            Error::InvalidCount => RawError::InvalidCount,
+            Error::NoStart => RawError::NoStart,
+            Error::NoOccurrence(at) => RawError::NoOccurrence(WallTime(at)),
+            Error::InvalidDuration => RawError::InvalidDuration,
+            Error::SearchLimit => RawError::SearchLimit,
+            Error::UnexpectedToken {
+                position,
+                expected,
+                found,
+            } => RawError::UnexpectedToken {
+                position,
+                expected,
+                found,
+            },
+            Error::UnexpectedEnd { position, expected } => {
+                RawError::UnexpectedEnd { position, expected }
+            }
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Error {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match RawError::deserialize(deserializer)? {
+            RawError::Empty => Error::Empty,
+            RawError::NoTime => Error::NoTime,
+            RawError::InvalidDayOfMonth(day) => Error::InvalidDayOfMonth(day),
+            RawError::InvalidNth(nth) => Error::InvalidNth(nth),
+            RawError::InvalidMonth(month) => Error::InvalidMonth(month),
+            RawError::InvalidInterval => Error::InvalidInterval,
+            RawError::InvalidWindow => Error::InvalidWindow,
+            RawError::InvalidStep => Error::InvalidStep,
+            RawError::InvalidRRule(part) => Error::InvalidRRule(part),
+            RawError::UnsupportedRRule(part) => Error::UnsupportedRRule(part),
+            RawError::InvalidCron(part) => Error::InvalidCron(part),
+            RawError::UnsupportedCron(part) => Error::UnsupportedCron(part),
+            RawError::UnknownTimeZone(name) => Error::UnknownTimeZone(name),
+            RawError::InvalidCount => Error::InvalidCount,
+            RawError::NoStart => Error::NoStart,
+            RawError::NoOccurrence(at) => Error::NoOccurrence(at.0),
+            RawError::InvalidDuration => Error::InvalidDuration,
+            RawError::SearchLimit => Error::SearchLimit,
+            RawError::UnexpectedToken {
+                position,
+                expected,
+                found,
+            } => Error::UnexpectedToken {
+                position,
+                expected,
+                found,
+            },
+            RawError::UnexpectedEnd { position, expected } => {
+                Error::UnexpectedEnd { position, expected }
+            }
+        })
+    }
+}
+
+/// As its expression, e.g. `"0 9 * * 1-5"`, see [`Cron::to_cron`].
+impl Serialize for Cron {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let expression = self.to_cron().map_err(ser::Error::custom)?;
+        serializer.serialize_str(&expression)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cron {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// `{"days": [1, -1], "time": "09:00", "calendar": {...}}`.
+#[derive(Serialize, Deserialize)]
+struct RawBusinessDays<C> {
+    days: Vec<i8>,
+    #[serde(flatten)]
+    times: RawTimes,
+    calendar: C,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start: Option<WallTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    until: Option<WallTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    count: Option<u32>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    gap: Gap,
+    #[serde(default, skip_serializing_if = "is_default")]
+    fold: Fold,
+}
+
+impl<C: HolidayCalendar + Serialize> Serialize for BusinessDays<C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RawBusinessDays {
+            days: self.days.iter().copied().collect(),
+            times: RawTimes::new(&self.times),
+            calendar: self.calendar(),
+            start: self.start().map(WallTime),
+            until: self.until().map(WallTime),
+            count: self.count(),
+            gap: self.gap,
+            fold: self.fold,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, C: HolidayCalendar + Deserialize<'de>> Deserialize<'de> for BusinessDays<C> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawBusinessDays::<C>::deserialize(deserializer)?;
+        let times = raw.times.list()?;
+        let mut days =
+            BusinessDays::new(&raw.days, times, raw.calendar).map_err(de::Error::custom)?;
+        if let Some(every) = raw.times.every().map_err(de::Error::custom)? {
+            days.times = every;
+        }
+        if let Some(start) = raw.start {
+            days = days.with_start(start.0);
+        }
+        if let Some(until) = raw.until {
+            days = days.with_until(until.0);
+        }
+        if let Some(count) = raw.count {
+            days = days.with_count(count).map_err(de::Error::custom)?;
+        }
+        Ok(days.with_gap(raw.gap).with_fold(raw.fold))
+    }
+}
+
+/// The recurrence with the name of its zone: `{"days": ["Mon"], "time": "09:00", "timezone":
+/// "Europe/Paris"}`.
+#[cfg(feature = "chrono-tz")]
+#[derive(Serialize, Deserialize)]
+struct RawZoned<R> {
+    #[serde(flatten)]
+    recurrence: R,
+    timezone: String,
+}
+
+#[cfg(feature = "chrono-tz")]
+impl<D: RecurrentDay + Serialize> Serialize for Zoned<D, chrono_tz::Tz> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RawZoned {
+            recurrence: self.recurrence(),
+            timezone: self.timezone().name().to_string(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "chrono-tz")]
+impl<'de, D: RecurrentDay + Deserialize<'de>> Deserialize<'de> for Zoned<D, chrono_tz::Tz> {
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        let raw = RawZoned::<Recurrence<D>>::deserialize(deserializer)?;
+        Zoned::named(raw.recurrence, &raw.timezone).map_err(de::Error::custom)
+    }
+}
+
+/// `{"recurrent": ..., "duration": "1h"}`, durations being whole seconds.
+#[derive(Serialize, Deserialize)]
+struct RawEvent<R> {
+    recurrent: R,
+    duration: Step,
+}
+
+impl<R: Recurrent + Serialize> Serialize for Event<R> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.duration().subsec_nanos() != 0 {
+            return Err(ser::Error::custom(
+                "duration must be a whole number of seconds",
+            ));
+        }
+        RawEvent {
+            recurrent: self.recurrent(),
+            duration: Step(self.duration()),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, R: Recurrent + Deserialize<'de>> Deserialize<'de> for Event<R> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawEvent::<R>::deserialize(deserializer)?;
+        Event::new(raw.recurrent, raw.duration.0).map_err(de::Error::custom)
+    }
+}
+
+/// `{"transitions": [{"recurrent": ..., "state": ...}]}`, in the order they were added.
+#[derive(Serialize, Deserialize)]
+struct RawStateMachine<R, S> {
+    transitions: Vec<RawTransition<R, S>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawTransition<R, S> {
+    recurrent: R,
+    state: S,
+}
+
+impl<S: Serialize, R: Recurrent + Serialize> Serialize for StateMachine<S, R> {
+    fn serialize<Se: Serializer>(&self, serializer: Se) -> Result<Se::Ok, Se::Error> {
+        RawStateMachine {
+            transitions: self
+                .transitions
+                .iter()
+                .map(|(recurrent, state)| RawTransition { recurrent, state })
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, S: Deserialize<'de>, R: Recurrent + Deserialize<'de>> Deserialize<'de>
+    for StateMachine<S, R>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawStateMachine::<R, S>::deserialize(deserializer)?;
+        Ok(raw
+            .transitions
+            .into_iter()
+            .fold(StateMachine::new(), |machine, transition| {
+                machine.with_transition(transition.recurrent, transition.state)
+            }))
+    }
+}
+
+/// `{"month": 12, "day": 25}`, `{"easter": -2}` or `"2021-07-30"`.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum RawHoliday {
+    Yearly(DayOfYear),
+    Easter { easter: i64 },
+    Date(Date),
+}
+
+fn weekend() -> BTreeSet<OrderedWeekday> {
+    Holidays::default().weekend
+}
+
+fn is_weekend(days: &BTreeSet<OrderedWeekday>) -> bool {
+    *days == weekend()
+}
+
+/// `{"weekend": ["Fri", "Sat"], "holidays": [...]}`, saturday and sunday being the default
+/// weekend.
+#[derive(Serialize, Deserialize)]
+struct RawHolidays {
+    #[serde(default = "weekend", skip_serializing_if = "is_weekend")]
+    weekend: BTreeSet<OrderedWeekday>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    holidays: Vec<RawHoliday>,
+}
+
+impl Serialize for Holidays {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RawHolidays {
+            weekend: self.weekend.clone(),
+            holidays: self
+                .holidays
+                .iter()
+                .map(|holiday| match holiday {
+                    Holiday::Yearly(day) => RawHoliday::Yearly(*day),
+                    Holiday::Easter(easter) => RawHoliday::Easter { easter: *easter },
+                    Holiday::Date(date) => RawHoliday::Date(Date(*date)),
+                })
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Holidays {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawHolidays::deserialize(deserializer)?;
+        Ok(Holidays {
+            weekend: raw.weekend,
+            holidays: raw
+                .holidays
+                .into_iter()
+                .map(|holiday| match holiday {
+                    RawHoliday::Yearly(day) => Holiday::Yearly(day),
+                    RawHoliday::Easter { easter } => Holiday::Easter(easter),
+                    RawHoliday::Date(date) => Holiday::Date(date.0),
+                })
+                .collect(),
+        })
+    }
+}
+
+/// `{"recurrent": ..., "calendar": {...}, "adjustment": "following"}`.
+#[derive(Serialize, Deserialize)]
+struct RawAdjusted<R, C> {
+    recurrent: R,
+    calendar: C,
+    adjustment: Adjustment,
+}
+
+impl<R: Serialize, C: Serialize> Serialize for Adjusted<R, C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RawAdjusted {
+            recurrent: &self.recurrent,
+            calendar: &self.calendar,
+            adjustment: self.adjustment,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, R, C> Deserialize<'de> for Adjusted<R, C>
+where
+    R: Recurrent + Deserialize<'de>,
+    C: HolidayCalendar + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawAdjusted::deserialize(deserializer)?;
+        Ok(Adjusted::new(raw.recurrent, raw.calendar, raw.adjustment))
+    }
+}
+
+/// Serializes combinations as `{"<name>": [a, b]}`, e.g. `{"union": [..., ...]}`.
+macro_rules! combination {
+    ($($combination:ident($raw:ident, $name:expr)),*) => {$(
+        #[derive(Serialize, Deserialize)]
+        struct $raw<A, B> {
+            #[serde(rename = $name)]
+            sides: (A, B),
+        }
+
+        impl<A: Serialize, B: Serialize> Serialize for $combination<A, B> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                $raw {
+                    sides: (&self.a, &self.b),
+                }
+                .serialize(serializer)
+            }
+        }
+
+        impl<'de, A: Deserialize<'de>, B: Deserialize<'de>> Deserialize<'de>
+            for $combination<A, B>
+        {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let (a, b) = $raw::deserialize(deserializer)?.sides;
+                Ok($combination::new(a, b))
+            }
+        }
+    )*};
+}
+
+combination!(
+    Union(RawUnion, "union"),
+    Intersection(RawIntersection, "intersection"),
+    Difference(RawDifference, "difference")
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Adjusted, Adjustment, BusinessDays, Cron, DayOfMonth, DayOfYear, Difference, Error, Event,
+        Holidays, MissingDay, OrderedWeekday, Recurrence, Recurrent, Schedule, StateMachine, Union,
+        WeekStart,
+    };
+    use chrono::{Duration, NaiveDate, NaiveTime, Weekday};
+    use serde_json::json;
+
+    fn nine() -> NaiveTime {
+        NaiveTime::from_hms_opt(9, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn errors() {
+        let at = NaiveDate::from_ymd_opt(2020, 9, 9)
+            .unwrap()
+            .and_time(nine());
+        for (error, value) in [
+            (Error::Empty, json!("empty")),
+            (Error::InvalidNth(24), json!({"invalid_nth": 24})),
+            (
+                Error::InvalidRRule("BYDAY=XX".to_string()),
+                json!({"invalid_rrule": "BYDAY=XX"}),
+            ),
+            (
+                Error::NoOccurrence(at),
+                json!({"no_occurrence": "2020-09-09T09:00:00"}),
+            ),
+            (
+                Error::UnexpectedEnd {
+                    position: 7,
+                    expected: "a time".to_string(),
+                },
+                json!({"unexpected_end": {"position": 7, "expected": "a time"}}),
+            ),
+        ] {
+            assert_eq!(serde_json::to_value(&error).unwrap(), value);
+            assert_eq!(serde_json::from_value::<Error>(value).unwrap(), error);
+        }
+    }
+
+    #[test]
+    fn weekly() {
+        let recurrence = Recurrence::new((Weekday::Mon, Weekday::Fri), nine()).unwrap();
+        let value = json!({"days": ["Mon", "Fri"], "time": "09:00"});
+        assert_eq!(serde_json::to_value(&recurrence).unwrap(), value);
+        assert_eq!(
+            serde_json::from_value::<Recurrence<OrderedWeekday>>(value).unwrap(),
+            recurrence
+        );
+
+        let recurrence = Recurrence::every(
+            Weekday::Sat,
+            nine(),
+            NaiveTime::from_hms_opt(17, 30, 0).unwrap(),
+            Duration::minutes(15),
+        )
+        .unwrap()
+        .with_interval(2, NaiveDate::from_ymd_opt(2020, 9, 5).unwrap())
+        .unwrap()
+        .with_start(
+            NaiveDate::from_ymd_opt(2020, 9, 5)
+                .unwrap()
+                .and_time(nine()),
+        )
+        .with_count(10)
+        .unwrap()
+        .with_week_start(WeekStart::SUNDAY);
+        let value = json!({
+            "days": ["Sat"],
+            "every": {"from": "09:00", "to": "17:30", "step": "15m"},
+            "interval": {"every": 2, "anchor": "2020-09-05"},
+            "start": "2020-09-05T09:00:00",
+            "count": 10,
+            "week_start": "Sun",
+        });
+        assert_eq!(serde_json::to_value(&recurrence).unwrap(), value);
+        assert_eq!(
+            serde_json::from_value::<Recurrence<OrderedWeekday>>(value).unwrap(),
+            recurrence
+        );
+    }
+
+    #[test]
+    fn other_days() {
+        let recurrence = Recurrence::monthly(&[1, 31], nine(), MissingDay::Clamp).unwrap();
+        let json = serde_json::to_string(&recurrence).unwrap();
+        assert_eq!(
+            json,
+            r#"{"days":[{"day":1,"missing":"clamp"},{"day":31,"missing":"clamp"}],"time":"09:00"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Recurrence<DayOfMonth>>(&json).unwrap(),
+            recurrence
+        );
+        assert_eq!(
+            serde_json::from_str::<Recurrence<DayOfMonth>>(r#"{"days":[15],"time":"09:00"}"#)
+                .unwrap(),
+            Recurrence::monthly(&[15], nine(), MissingDay::Skip).unwrap()
+        );
+
+        let recurrence = Recurrence::yearly(
+            &[
+                DayOfYear::nth_weekday(11, 4, Weekday::Thu).unwrap(),
+                DayOfYear::date(12, 25, MissingDay::Skip).unwrap(),
+            ],
+            nine(),
+        )
+        .unwrap();
+        let value = serde_json::to_value(&recurrence).unwrap();
+        assert_eq!(
+            value["days"],
+            json!([{"month": 11, "nth": 4, "weekday": "Thu"}, {"month": 12, "day": 25}])
+        );
+        assert_eq!(
+            serde_json::from_value::<Recurrence<DayOfYear>>(value).unwrap(),
+            recurrence
+        );
+    }
+
+    #[test]
+    fn schedule() {
+        let mut schedule: Schedule<_> = Recurrence::new(Weekday::Wed, nine()).unwrap().into();
+        let at = |day| {
+            NaiveDate::from_ymd_opt(2020, 9, day)
+                .unwrap()
+                .and_time(nine())
+        };
+        schedule.exclude_date(NaiveDate::from_ymd_opt(2020, 9, 2).unwrap());
+        schedule.add(at(5));
+        schedule.reschedule(at(9), at(10)).unwrap();
+        let value = serde_json::to_value(&schedule).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "days": ["Wed"],
+                "time": "09:00",
+                "exclude_dates": ["2020-09-02"],
+                "add": ["2020-09-05T09:00:00"],
+                "reschedule": {"2020-09-09T09:00:00": "2020-09-10T09:00:00"},
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<Schedule<OrderedWeekday>>(value).unwrap(),
+            schedule
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let error = |value| {
+            serde_json::from_value::<Recurrence<OrderedWeekday>>(value)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            error(json!({"days": [], "time": "09:00"})),
+            crate::Error::Empty.to_string()
+        );
+        assert_eq!(
+            error(json!({"days": ["Mon"]})),
+            crate::Error::NoTime.to_string()
+        );
+        assert!(error(json!({"days": ["Mon"], "time": "25:00"})).contains("invalid Time"));
+        assert!(serde_json::from_value::<Recurrence<DayOfMonth>>(json!({
+            "days": [32],
+            "time": "09:00",
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn recurrents() {
+        let cron: Cron = "0 9 * * MON-FRI".parse().unwrap();
+        assert_eq!(serde_json::to_value(&cron).unwrap(), json!("0 9 * * 1-5"));
+        assert_eq!(
+            serde_json::from_value::<Cron>(json!("@daily")).unwrap(),
+            "0 0 * * *".parse().unwrap()
+        );
+        let last_day = Recurrence::monthly(&[-1], nine(), MissingDay::Skip).unwrap();
+        assert!(serde_json::to_value(Cron::Monthly(last_day)).is_err());
+
+        let holidays = Holidays::new()
+            .with_weekend((Weekday::Fri, Weekday::Sat))
+            .with_fixed(12, 25)
+            .unwrap()
+            .with_easter(-2)
+            .with_date(NaiveDate::from_ymd_opt(2021, 7, 30).unwrap());
+        let days = BusinessDays::new(&[1, -1], nine(), holidays.clone()).unwrap();
+        let value = json!({
+            "days": [-1, 1],
+            "time": "09:00",
+            "calendar": {
+                "weekend": ["Fri", "Sat"],
+                "holidays": [{"month": 12, "day": 25}, {"easter": -2}, "2021-07-30"],
+            },
+        });
+        assert_eq!(serde_json::to_value(&days).unwrap(), value);
+        assert_eq!(
+            serde_json::from_value::<BusinessDays<Holidays>>(value).unwrap(),
+            days
+        );
+
+        let payroll = cron
+            .clone()
+            .difference(days)
+            .adjusted(Holidays::new(), Adjustment::ModifiedFollowing);
+        let value = serde_json::to_value(&payroll).unwrap();
+        assert_eq!(value["recurrent"]["difference"][0], json!("0 9 * * 1-5"));
+        assert_eq!(value["calendar"], json!({}));
+        assert_eq!(value["adjustment"], json!("modified_following"));
+        assert_eq!(
+            serde_json::from_value::<Adjusted<Difference<Cron, BusinessDays<Holidays>>, Holidays>>(
+                value
+            )
+            .unwrap(),
+            payroll
+        );
+
+        let event = Event::new(cron.clone().union(cron.clone()), Duration::hours(8)).unwrap();
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(
+            value,
+            json!({"recurrent": {"union": ["0 9 * * 1-5", "0 9 * * 1-5"]}, "duration": "8h"})
+        );
+        assert_eq!(
+            serde_json::from_value::<Event<Union<Cron, Cron>>>(value).unwrap(),
+            event
+        );
+        assert!(serde_json::from_value::<Event<Cron>>(
+            json!({"recurrent": "@daily", "duration": "0s"})
+        )
+        .is_err());
+
+        let machine = StateMachine::new()
+            .with_transition(cron.clone(), "on".to_string())
+            .with_transition("0 18 * * *".parse().unwrap(), "off".to_string());
+        let value = serde_json::to_value(&machine).unwrap();
+        assert_eq!(
+            value,
+            json!({"transitions": [
+                {"recurrent": "0 9 * * 1-5", "state": "on"},
+                {"recurrent": "0 18 * * *", "state": "off"},
+            ]})
+        );
+        assert_eq!(
+            serde_json::from_value::<StateMachine<String, Cron>>(value).unwrap(),
+            machine
+        );
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    #[test]
+    fn zoned() {
+        let zoned = Recurrence::new(Weekday::Mon, nine())
+            .unwrap()
+            .in_timezone(chrono_tz::Europe::Paris);
+        let value = json!({"days": ["Mon"], "time": "09:00", "timezone": "Europe/Paris"});
+        assert_eq!(serde_json::to_value(&zoned).unwrap(), value);
+        assert_eq!(
+            serde_json::from_value::<crate::Zoned<OrderedWeekday, chrono_tz::Tz>>(value).unwrap(),
+            zoned
+        );
+        assert!(
+            serde_json::from_value::<crate::Zoned<OrderedWeekday, chrono_tz::Tz>>(
+                json!({"days": ["Mon"], "time": "09:00", "timezone": "Europe/Nowhere"})
+            )
+            .is_err()
+        );
+    }
+}
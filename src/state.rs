@@ -0,0 +1,224 @@
+use crate::Recurrent;
+use chrono::{DateTime, Duration, TimeZone};
+
+/// States switched to over and over, e.g. a thermostat on at 08:00 on weekdays, off at 18:00
+/// and in eco mode from saturday.
+///
+/// Each transition switches to its state whenever its [`Recurrent`] occurs, until the next
+/// transition occurs: the state at any time is the one of the latest transition. When several
+/// transitions occur together, the one added last wins.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StateMachine<S, R: Recurrent> {
+    pub(crate) transitions: Vec<(R, S)>,
+}
+
+/// A switch of a [`StateMachine`] to a state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Transition<'a, S, T: TimeZone> {
+    at: DateTime<T>,
+    state: &'a S,
+}
+
+impl<'a, S, T: TimeZone> Transition<'a, S, T> {
+    pub fn at(&self) -> &DateTime<T> {
+        &self.at
+    }
+
+    /// The state switched to, possibly the same as before.
+    pub fn state(&self) -> &'a S {
+        self.state
+    }
+}
+
+impl<S, R: Recurrent> Default for StateMachine<S, R> {
+    fn default() -> Self {
+        StateMachine {
+            transitions: vec![],
+        }
+    }
+}
+
+impl<S, R: Recurrent> StateMachine<S, R> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Switches to `state` whenever `recurrent` occurs.
+    pub fn with_transition(mut self, recurrent: R, state: S) -> Self {
+        self.transitions.push((recurrent, state));
+        self
+    }
+
+    /// The state at `date`, set by the latest transition at or before it.
+    pub fn state_at<T: TimeZone>(&self, date: &DateTime<T>) -> Option<&S> {
+        self.prev_transition(&(date.clone() + Duration::nanoseconds(1)))
+            .map(|transition| transition.state)
+    }
+
+    /// First transition strictly after `date`.
+    pub fn next_transition<T: TimeZone>(&self, date: &DateTime<T>) -> Option<Transition<'_, S, T>> {
+        self.transitions
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (recurrent, state))| {
+                let at = recurrent.next(date)?;
+                Some((at, index, state))
+            })
+            // the earliest, then the last added
+            .min_by(|(a, i, _), (b, j, _)| a.cmp(b).then(j.cmp(i)))
+            .map(|(at, _, state)| Transition { at, state })
+    }
+
+    /// Last transition strictly before `date`.
+    pub fn prev_transition<T: TimeZone>(&self, date: &DateTime<T>) -> Option<Transition<'_, S, T>> {
+        self.transitions
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (recurrent, state))| {
+                let at = recurrent.prev(date)?;
+                Some((at, index, state))
+            })
+            // the latest, then the last added
+            .max_by(|(a, i, _), (b, j, _)| a.cmp(b).then(i.cmp(j)))
+            .map(|(at, _, state)| Transition { at, state })
+    }
+
+    /// Lazily iterates over the transitions strictly after `date`, in chronological order.
+    pub fn transitions_after<T: TimeZone>(&self, date: &DateTime<T>) -> Transitions<'_, S, R, T> {
+        Transitions {
+            machine: self,
+            front: Some(date.clone()),
+            back: None,
+        }
+    }
+
+    /// Lazily iterates over the transitions in `[start, end)`.
+    pub fn transitions_between<T: TimeZone>(
+        &self,
+        start: &DateTime<T>,
+        end: &DateTime<T>,
+    ) -> Transitions<'_, S, R, T> {
+        Transitions {
+            machine: self,
+            front: Some(start.clone() - Duration::nanoseconds(1)),
+            back: Some(end.clone()),
+        }
+    }
+}
+
+/// Lazy iterator over the [`Transition`]s of a [`StateMachine`], see
+/// [`Occurrences`](crate::Occurrences).
+#[derive(Clone, Debug)]
+pub struct Transitions<'a, S, R: Recurrent, T: TimeZone> {
+    machine: &'a StateMachine<S, R>,
+    front: Option<DateTime<T>>,
+    back: Option<DateTime<T>>,
+}
+
+impl<'a, S, R: Recurrent, T: TimeZone> Iterator for Transitions<'a, S, R, T> {
+    type Item = Transition<'a, S, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.machine.next_transition(self.front.as_ref()?)?;
+        if matches!(&self.back, Some(back) if next.at >= *back) {
+            return None;
+        }
+        self.front = Some(next.at.clone());
+        Some(next)
+    }
+}
+
+impl<'a, S, R: Recurrent, T: TimeZone> DoubleEndedIterator for Transitions<'a, S, R, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let prev = self.machine.prev_transition(self.back.as_ref()?)?;
+        if matches!(&self.front, Some(front) if prev.at <= *front) {
+            return None;
+        }
+        self.back = Some(prev.at.clone());
+        Some(prev)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{OrderedWeekday, Recurrence, StateMachine};
+    use chrono::{DateTime, NaiveTime, Utc, Weekday};
+
+    fn parse(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Mode {
+        On,
+        Off,
+        Eco,
+    }
+
+    #[test]
+    fn thermostat() {
+        let at = |hour| NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+        let thermostat = StateMachine::new()
+            .with_transition(Recurrence::new(Weekday::Mon, at(8)).unwrap(), Mode::On)
+            .with_transition(Recurrence::new(Weekday::Mon, at(18)).unwrap(), Mode::Off)
+            .with_transition(Recurrence::new(Weekday::Sat, at(0)).unwrap(), Mode::Eco);
+        // monday
+        assert_eq!(
+            thermostat.state_at(&parse("2020-09-07T07:59:59Z")),
+            Some(&Mode::Eco)
+        );
+        assert_eq!(
+            thermostat.state_at(&parse("2020-09-07T08:00:00Z")),
+            Some(&Mode::On)
+        );
+        assert_eq!(
+            thermostat.state_at(&parse("2020-09-09T12:00:00Z")),
+            Some(&Mode::Off)
+        );
+        let next = thermostat
+            .next_transition(&parse("2020-09-07T08:00:00Z"))
+            .unwrap();
+        assert_eq!(next.at(), &parse("2020-09-07T18:00:00Z"));
+        assert_eq!(next.state(), &Mode::Off);
+        assert_eq!(
+            thermostat
+                .transitions_between(
+                    &parse("2020-09-07T08:00:00Z"),
+                    &parse("2020-09-14T18:00:00Z")
+                )
+                .rev()
+                .map(|transition| (*transition.at(), transition.state()))
+                .collect::<Vec<_>>(),
+            vec![
+                (parse("2020-09-14T08:00:00Z"), &Mode::On),
+                (parse("2020-09-12T00:00:00Z"), &Mode::Eco),
+                (parse("2020-09-07T18:00:00Z"), &Mode::Off),
+                (parse("2020-09-07T08:00:00Z"), &Mode::On),
+            ]
+        );
+    }
+
+    #[test]
+    fn together() {
+        let noon = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+        let toggle = StateMachine::new()
+            .with_transition(Recurrence::new(Weekday::Mon, noon).unwrap(), false)
+            .with_transition(
+                Recurrence::new((Weekday::Mon, Weekday::Fri), noon).unwrap(),
+                true,
+            );
+        assert_eq!(toggle.state_at(&parse("2020-09-07T12:00:00Z")), Some(&true));
+        assert_eq!(
+            toggle
+                .transitions_after(&parse("2020-09-04T12:00:00Z"))
+                .next()
+                .map(|transition| *transition.state()),
+            Some(true)
+        );
+        assert_eq!(
+            StateMachine::<bool, Recurrence<OrderedWeekday>>::new()
+                .state_at(&parse("2020-09-07T12:00:00Z")),
+            None
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use crate::Error;
+use chrono::{Duration, NaiveTime};
+use std::collections::BTreeSet;
+use std::ops::Bound;
+
+/// The times of day a [`Recurrence`](crate::Recurrence) occurs at.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum DayTimes {
+    /// Some given times.
+    List(BTreeSet<NaiveTime>),
+    /// Every `step` seconds from `from` up to and including `to`.
+    Every {
+        from: NaiveTime,
+        to: NaiveTime,
+        step: i64,
+    },
+}
+
+impl DayTimes {
+    pub(crate) fn every(from: NaiveTime, to: NaiveTime, step: Duration) -> Result<Self, Error> {
+        if to < from {
+            return Err(Error::InvalidWindow);
+        }
+        if step <= Duration::zero()
+            || step >= Duration::days(1)
+            || step.num_nanoseconds() != Some(step.num_seconds() * 1_000_000_000)
+        {
+            return Err(Error::InvalidStep);
+        }
+        Ok(DayTimes::Every {
+            from,
+            to,
+            step: step.num_seconds(),
+        })
+    }
+
+    /// Number of times in a day.
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            DayTimes::List(times) => times.len(),
+            DayTimes::Every { from, to, step } => ((*to - *from).num_seconds() / step) as usize + 1,
+        }
+    }
+
+    pub(crate) fn first(&self) -> NaiveTime {
+        match self {
+            DayTimes::List(times) => *times.iter().next().unwrap(),
+            DayTimes::Every { from, .. } => *from,
+        }
+    }
+
+    pub(crate) fn last(&self) -> NaiveTime {
+        match self {
+            DayTimes::List(times) => *times.iter().next_back().unwrap(),
+            DayTimes::Every { .. } => self.nth(self.len() - 1),
+        }
+    }
+
+    /// First time strictly after `time`.
+    pub(crate) fn after(&self, time: NaiveTime) -> Option<NaiveTime> {
+        match self {
+            DayTimes::List(times) => times
+                .range((Bound::Excluded(time), Bound::Unbounded))
+                .next()
+                .copied(),
+            DayTimes::Every { from, step, .. } => {
+                if time < *from {
+                    return Some(*from);
+                }
+                let elapsed = nanos(time - *from);
+                let index = elapsed / (step * 1_000_000_000) + 1;
+                self.get(index as usize)
+            }
+        }
+    }
+
+    /// Last time strictly before `time`.
+    pub(crate) fn before(&self, time: NaiveTime) -> Option<NaiveTime> {
+        match self {
+            DayTimes::List(times) => times.range(..time).next_back().copied(),
+            DayTimes::Every { from, step, .. } => {
+                if time <= *from {
+                    return None;
+                }
+                let elapsed = nanos(time - *from) - 1;
+                let index = (elapsed / (step * 1_000_000_000)) as usize;
+                Some(self.nth(index.min(self.len() - 1)))
+            }
+        }
+    }
+
+    /// Number of times up to and including `time`.
+    pub(crate) fn count_until(&self, time: NaiveTime) -> usize {
+        match self {
+            DayTimes::List(times) => times.range(..=time).count(),
+            DayTimes::Every { from, step, .. } => {
+                if time < *from {
+                    return 0;
+                }
+                let elapsed = nanos(time - *from);
+                let count = (elapsed / (step * 1_000_000_000)) as usize + 1;
+                count.min(self.len())
+            }
+        }
+    }
+
+    /// All the times, in order.
+    pub(crate) fn to_set(&self) -> BTreeSet<NaiveTime> {
+        match self {
+            DayTimes::List(times) => times.clone(),
+            DayTimes::Every { .. } => (0..self.len()).map(|index| self.nth(index)).collect(),
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<NaiveTime> {
+        if index < self.len() {
+            Some(self.nth(index))
+        } else {
+            None
+        }
+    }
+
+    fn nth(&self, index: usize) -> NaiveTime {
+        match self {
+            DayTimes::List(times) => *times.iter().nth(index).unwrap(),
+            DayTimes::Every { from, step, .. } => *from + Duration::seconds(step * index as i64),
+        }
+    }
+}
+
+fn nanos(duration: Duration) -> i64 {
+    duration.num_nanoseconds().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Recurrence, Recurrent};
+    use chrono::{DateTime, Duration, NaiveTime, Utc, Weekday};
+
+    fn parse(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn health_checks() -> Recurrence<crate::OrderedWeekday> {
+        Recurrence::every(
+            (
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ),
+            NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+            Duration::minutes(15),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn every() {
+        let recurrence = health_checks();
+        // friday
+        assert_eq!(
+            recurrence.next(&parse("2020-09-04T07:00:00Z")).unwrap(),
+            parse("2020-09-04T08:00:00Z")
+        );
+        assert_eq!(
+            recurrence.next(&parse("2020-09-04T08:00:00Z")).unwrap(),
+            parse("2020-09-04T08:15:00Z")
+        );
+        assert_eq!(
+            recurrence.next(&parse("2020-09-04T12:07:30.5Z")).unwrap(),
+            parse("2020-09-04T12:15:00Z")
+        );
+        assert_eq!(
+            recurrence.next(&parse("2020-09-04T18:00:00Z")).unwrap(),
+            parse("2020-09-07T08:00:00Z")
+        );
+        assert_eq!(
+            recurrence.prev(&parse("2020-09-04T12:15:00Z")).unwrap(),
+            parse("2020-09-04T12:00:00Z")
+        );
+        assert_eq!(
+            recurrence.prev(&parse("2020-09-04T12:15:00.001Z")).unwrap(),
+            parse("2020-09-04T12:15:00Z")
+        );
+        assert_eq!(
+            recurrence.prev(&parse("2020-09-07T08:00:00Z")).unwrap(),
+            parse("2020-09-04T18:00:00Z")
+        );
+        assert_eq!(
+            recurrence.prev(&parse("2020-09-05T12:00:00Z")).unwrap(),
+            parse("2020-09-04T18:00:00Z")
+        );
+
+        let start = parse("2020-09-03T17:00:00Z");
+        let end = parse("2020-09-08T08:30:00Z");
+        assert_eq!(
+            recurrence.count_between(&start, &end, true),
+            5 + 41 + 41 + 3
+        );
+        assert_eq!(
+            recurrence.count_between(&start, &end, true),
+            recurrence.between(&start, &end, true).count()
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let eight = NaiveTime::from_hms_opt(8, 0, 0).unwrap();
+        let six = NaiveTime::from_hms_opt(18, 0, 0).unwrap();
+        assert!(matches!(
+            Recurrence::every(Weekday::Mon, six, eight, Duration::minutes(15)),
+            Err(Error::InvalidWindow)
+        ));
+        assert!(matches!(
+            Recurrence::every(Weekday::Mon, eight, six, Duration::zero()),
+            Err(Error::InvalidStep)
+        ));
+        assert!(matches!(
+            Recurrence::every(Weekday::Mon, eight, six, Duration::milliseconds(1500)),
+            Err(Error::InvalidStep)
+        ));
+    }
+}
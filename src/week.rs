@@ -0,0 +1,139 @@
+use crate::locale::WEEKDAYS;
+use crate::OrderedWeekday;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+/// The day weeks start on: Monday for ISO 8601 weeks, the default, Sunday in the US...
+///
+/// Recurrences repeating every n weeks count weeks from it, and week-based queries and
+/// descriptions follow it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WeekStart(OrderedWeekday);
+
+impl WeekStart {
+    /// ISO 8601 weeks.
+    pub const MONDAY: WeekStart = WeekStart(OrderedWeekday::Mon);
+    /// US weeks.
+    pub const SUNDAY: WeekStart = WeekStart(OrderedWeekday::Sun);
+
+    pub fn new(weekday: Weekday) -> Self {
+        WeekStart(weekday.into())
+    }
+
+    pub fn weekday(self) -> OrderedWeekday {
+        self.0
+    }
+
+    /// Position of `weekday` in the week, 0 for the first day.
+    pub fn position(self, weekday: OrderedWeekday) -> usize {
+        (weekday as usize + 7 - self.0 as usize) % 7
+    }
+
+    /// The days of the week, in order.
+    pub fn days(self) -> impl Iterator<Item = OrderedWeekday> {
+        (0..7).map(move |offset| WEEKDAYS[(self.0 as usize + offset) % 7])
+    }
+
+    /// First day of the week `date` falls in.
+    pub fn first_day(self, date: NaiveDate) -> NaiveDate {
+        date - Duration::days(self.position(date.weekday().into()) as i64)
+    }
+
+    /// Index of the week `date` falls in.
+    pub(crate) fn week(self, date: NaiveDate) -> i64 {
+        // 0001-01-01 is a monday
+        (date.num_days_from_ce() as i64 - 1 - self.0 as i64).div_euclid(7)
+    }
+}
+
+impl Default for WeekStart {
+    fn default() -> Self {
+        WeekStart::MONDAY
+    }
+}
+
+impl From<Weekday> for WeekStart {
+    fn from(weekday: Weekday) -> Self {
+        WeekStart::new(weekday)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{OrderedWeekday, Recurrence, Recurrent, WeekStart};
+    use chrono::{DateTime, NaiveDate, NaiveTime, Utc, Weekday};
+
+    fn parse(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn weeks() {
+        // a wednesday
+        let date = NaiveDate::from_ymd_opt(2020, 9, 2).unwrap();
+        assert_eq!(
+            WeekStart::MONDAY.first_day(date),
+            NaiveDate::from_ymd_opt(2020, 8, 31).unwrap()
+        );
+        assert_eq!(
+            WeekStart::SUNDAY.first_day(date),
+            NaiveDate::from_ymd_opt(2020, 8, 30).unwrap()
+        );
+        assert_eq!(WeekStart::SUNDAY.position(OrderedWeekday::Sat), 6);
+        assert_eq!(
+            WeekStart::new(Weekday::Sat).days().collect::<Vec<_>>()[..2],
+            [OrderedWeekday::Sat, OrderedWeekday::Sun]
+        );
+    }
+
+    #[test]
+    fn intervals() {
+        let nine = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+        // sunday and monday, every other week from monday 2020-08-31
+        let anchor = NaiveDate::from_ymd_opt(2020, 8, 31).unwrap();
+        let iso = Recurrence::new((Weekday::Sun, Weekday::Mon), nine)
+            .unwrap()
+            .with_interval(2, anchor)
+            .unwrap();
+        let us = iso.clone().with_week_start(WeekStart::SUNDAY);
+        let after = parse("2020-08-29T00:00:00Z");
+        assert_eq!(
+            iso.occurrences_after(&after).take(4).collect::<Vec<_>>(),
+            vec![
+                parse("2020-08-31T09:00:00Z"),
+                parse("2020-09-06T09:00:00Z"),
+                parse("2020-09-14T09:00:00Z"),
+                parse("2020-09-20T09:00:00Z"),
+            ]
+        );
+        assert_eq!(
+            us.occurrences_after(&after).take(4).collect::<Vec<_>>(),
+            vec![
+                parse("2020-08-30T09:00:00Z"),
+                parse("2020-08-31T09:00:00Z"),
+                parse("2020-09-13T09:00:00Z"),
+                parse("2020-09-14T09:00:00Z"),
+            ]
+        );
+        let end = parse("2020-10-01T00:00:00Z");
+        for recurrence in &[&iso, &us] {
+            assert_eq!(
+                recurrence.count_between(&after, &end, false),
+                recurrence.between(&after, &end, false).count()
+            );
+        }
+
+        // this week's
+        let now = parse("2020-09-02T12:00:00Z");
+        assert_eq!(
+            us.occurrences_in_week(&now, WeekStart::SUNDAY)
+                .collect::<Vec<_>>(),
+            vec![parse("2020-08-30T09:00:00Z"), parse("2020-08-31T09:00:00Z")]
+        );
+        assert_eq!(
+            us.occurrences_in_week(&now, WeekStart::MONDAY)
+                .collect::<Vec<_>>(),
+            vec![parse("2020-08-31T09:00:00Z")]
+        );
+    }
+}
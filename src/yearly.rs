@@ -0,0 +1,227 @@
+use crate::{DayOfMonth, Error, MissingDay, NthWeekday, Recurrence, RecurrentDay, Times};
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::collections::BTreeSet;
+
+/// A day of year: a fixed date or the nth weekday of a month.
+///
+/// Fixed dates that don't exist every year (February 29th) follow their [`MissingDay`] policy:
+/// skip the year, clamp to February 28th or roll over to March 1st.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Ord, PartialOrd)]
+pub struct DayOfYear {
+    month: u32,
+    day: YearlyDay,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Ord, PartialOrd)]
+pub(crate) enum YearlyDay {
+    Date(DayOfMonth),
+    Nth(NthWeekday),
+}
+
+impl DayOfYear {
+    /// The given date of every year, e.g. `DayOfYear::date(2, 29, MissingDay::Clamp)`.
+    pub fn date(month: u32, day: u32, missing: MissingDay) -> Result<Self, Error> {
+        check_month(month)?;
+        // a leap year has all dates
+        if day < 1 || day > crate::days_in_month(2000, month) {
+            return Err(Error::InvalidDayOfMonth(day.min(i8::MAX as u32) as i8));
+        }
+        Ok(DayOfYear {
+            month,
+            day: YearlyDay::Date(DayOfMonth::new(day as i8, missing)?),
+        })
+    }
+
+    /// The nth weekday of the given month of every year, e.g. the fourth Thursday of November.
+    pub fn nth_weekday(month: u32, nth: i8, weekday: Weekday) -> Result<Self, Error> {
+        check_month(month)?;
+        Ok(DayOfYear {
+            month,
+            day: YearlyDay::Nth(NthWeekday::new(nth, weekday)?),
+        })
+    }
+
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    pub(crate) fn day(&self) -> YearlyDay {
+        self.day
+    }
+
+    /// The date this day falls on in the given year, if any.
+    fn resolve(&self, year: i32) -> Option<NaiveDate> {
+        match self.day {
+            YearlyDay::Date(day) => day.resolve(year, self.month),
+            YearlyDay::Nth(nth) => nth.resolve(year, self.month),
+        }
+    }
+
+    /// Longest stretch of years between two occurrences, over the 400 years the Gregorian
+    /// calendar repeats after.
+    fn max_gap(&self) -> i32 {
+        match self.day {
+            // skipped from 1896 to 1904
+            YearlyDay::Date(day) if day.day() == 29 && day.missing() == MissingDay::Skip => 8,
+            // five of a weekday in February, only in leap years starting on it
+            YearlyDay::Nth(nth) if nth.nth().abs() == 5 && self.month == 2 => 40,
+            YearlyDay::Nth(nth) if nth.nth().abs() == 5 => 6,
+            _ => 1,
+        }
+    }
+}
+
+fn check_month(month: u32) -> Result<(), Error> {
+    if (1..=12).contains(&month) {
+        Ok(())
+    } else {
+        Err(Error::InvalidMonth(month))
+    }
+}
+
+/// Longest stretch of years between two occurrences of any of `days`.
+fn max_gap(days: &BTreeSet<DayOfYear>) -> i32 {
+    days.iter().map(DayOfYear::max_gap).min().unwrap_or(1)
+}
+
+impl RecurrentDay for DayOfYear {
+    fn falls_on(&self, date: NaiveDate) -> bool {
+        self.resolve(date.year()) == Some(date)
+    }
+
+    fn period(date: NaiveDate) -> i64 {
+        date.year() as i64
+    }
+
+    // the Gregorian calendar repeats after 400 years
+    const CYCLE: i64 = 400;
+
+    fn next_date(days: &BTreeSet<Self>, date: NaiveDate) -> Option<NaiveDate> {
+        (date.year()..=date.year() + max_gap(days))
+            .flat_map(|year| days.iter().filter_map(move |day| day.resolve(year)))
+            .filter(|d| *d > date)
+            .min()
+    }
+
+    fn prev_date(days: &BTreeSet<Self>, date: NaiveDate) -> Option<NaiveDate> {
+        (date.year() - max_gap(days)..=date.year())
+            .flat_map(|year| days.iter().filter_map(move |day| day.resolve(year)))
+            .filter(|d| *d < date)
+            .max()
+    }
+}
+
+impl Recurrence<DayOfYear> {
+    /// Repeats on the given days of every year, see [`DayOfYear`].
+    pub fn yearly<S: Times>(days: &[DayOfYear], times: S) -> Result<Self, Error> {
+        Self::from_days(days.iter().copied().collect(), times)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DayOfYear, Error, MissingDay, Recurrence, Recurrent};
+    use chrono::{DateTime, NaiveDate, NaiveTime, Utc, Weekday};
+
+    fn parse(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn leap_day(missing: MissingDay) -> Vec<DateTime<Utc>> {
+        let noon = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+        Recurrence::yearly(&[DayOfYear::date(2, 29, missing).unwrap()], noon)
+            .unwrap()
+            .occurrences_after(&parse("2020-02-29T12:00:00Z"))
+            .take(2)
+            .collect()
+    }
+
+    #[test]
+    fn leap_day_policies() {
+        assert_eq!(
+            leap_day(MissingDay::Skip),
+            vec![parse("2024-02-29T12:00:00Z"), parse("2028-02-29T12:00:00Z")]
+        );
+        assert_eq!(
+            leap_day(MissingDay::Clamp),
+            vec![parse("2021-02-28T12:00:00Z"), parse("2022-02-28T12:00:00Z")]
+        );
+        assert_eq!(
+            leap_day(MissingDay::Rollover),
+            vec![parse("2021-03-01T12:00:00Z"), parse("2022-03-01T12:00:00Z")]
+        );
+        let noon = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+        let skip = [DayOfYear::date(2, 29, MissingDay::Skip).unwrap()];
+        assert_eq!(
+            Recurrence::yearly(&skip, noon)
+                .unwrap()
+                .prev(&parse("2020-02-29T12:00:00Z"))
+                .unwrap(),
+            parse("2016-02-29T12:00:00Z")
+        );
+    }
+
+    #[test]
+    fn holidays() {
+        let noon = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+        let thanksgiving = DayOfYear::nth_weekday(11, 4, Weekday::Thu).unwrap();
+        let christmas = DayOfYear::date(12, 25, MissingDay::Skip).unwrap();
+        let recurrence = Recurrence::yearly(&[thanksgiving, christmas], noon).unwrap();
+        assert_eq!(
+            recurrence
+                .occurrences_after(&parse("2020-01-01T00:00:00Z"))
+                .take(3)
+                .collect::<Vec<_>>(),
+            vec![
+                parse("2020-11-26T12:00:00Z"),
+                parse("2020-12-25T12:00:00Z"),
+                parse("2021-11-25T12:00:00Z"),
+            ]
+        );
+        assert_eq!(
+            recurrence.prev(&parse("2020-11-26T12:00:00Z")).unwrap(),
+            parse("2019-12-25T12:00:00Z")
+        );
+    }
+
+    #[test]
+    fn rare_days() {
+        let noon = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+        // five Mondays in February, only in leap years starting on a Monday
+        let fifth_monday = [DayOfYear::nth_weekday(2, 5, Weekday::Mon).unwrap()];
+        let recurrence = Recurrence::yearly(&fifth_monday, noon).unwrap();
+        assert_eq!(
+            recurrence.next(&parse("2017-01-01T00:00:00Z")),
+            Some(parse("2044-02-29T12:00:00Z"))
+        );
+        assert_eq!(
+            recurrence.prev(&parse("2044-02-29T12:00:00Z")),
+            Some(parse("2016-02-29T12:00:00Z"))
+        );
+        // 40 years apart around 2100, not a leap year
+        assert_eq!(
+            recurrence.next(&parse("2072-03-01T00:00:00Z")),
+            Some(parse("2112-02-29T12:00:00Z"))
+        );
+        // every 4 years from 2021 never is a leap year
+        let leap_day = [DayOfYear::date(2, 29, MissingDay::Skip).unwrap()];
+        let never = Recurrence::yearly(&leap_day, noon)
+            .unwrap()
+            .with_interval(4, NaiveDate::from_ymd_opt(2021, 1, 1).unwrap())
+            .unwrap();
+        assert_eq!(never.next(&parse("2021-01-01T00:00:00Z")), None);
+        assert_eq!(never.prev(&parse("2021-01-01T00:00:00Z")), None);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(matches!(
+            DayOfYear::date(13, 1, MissingDay::Skip),
+            Err(Error::InvalidMonth(13))
+        ));
+        assert!(matches!(
+            DayOfYear::date(4, 31, MissingDay::Skip),
+            Err(Error::InvalidDayOfMonth(31))
+        ));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::{Fold, Gap, Recurrence, Recurrent, RecurrentDay};
+use chrono::{DateTime, TimeZone};
+
+/// A [`Recurrence`] bound to the time zone its times of day are wall times in.
+///
+/// Occurrences are computed in that zone, then converted back to the zone of the date they are
+/// asked from: a recurrence at 09:00 in Paris yields 07:00 or 08:00 UTC depending on the season.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Zoned<D: RecurrentDay, Z: TimeZone> {
+    recurrence: Recurrence<D>,
+    timezone: Z,
+}
+
+impl<D: RecurrentDay, Z: TimeZone> Zoned<D, Z> {
+    pub fn new(recurrence: Recurrence<D>, timezone: Z) -> Self {
+        Zoned {
+            recurrence,
+            timezone,
+        }
+    }
+
+    pub fn recurrence(&self) -> &Recurrence<D> {
+        &self.recurrence
+    }
+
+    pub fn timezone(&self) -> &Z {
+        &self.timezone
+    }
+}
+
+impl<D: RecurrentDay, Z: TimeZone> Recurrent for Zoned<D, Z> {
+    /// First occurrence strictly after `date`, in `date`'s time zone.
+    fn next<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>> {
+        self.recurrence
+            .next(&date.with_timezone(&self.timezone))
+            .map(|next| next.with_timezone(&date.timezone()))
+    }
+
+    /// Last occurrence strictly before `date`, in `date`'s time zone.
+    fn prev<T: TimeZone>(&self, date: &DateTime<T>) -> Option<DateTime<T>> {
+        self.recurrence
+            .prev(&date.with_timezone(&self.timezone))
+            .map(|prev| prev.with_timezone(&date.timezone()))
+    }
+
+    fn dst_policy(&self) -> (Gap, Fold) {
+        self.recurrence.dst_policy()
+    }
+}
+
+#[cfg(feature = "chrono-tz")]
+impl<D: RecurrentDay> Zoned<D, chrono_tz::Tz> {
+    /// Binds `recurrence` to an IANA time zone, e.g. `"Europe/Paris"`.
+    pub fn named(recurrence: Recurrence<D>, timezone: &str) -> Result<Self, crate::Error> {
+        let timezone = timezone
+            .parse()
+            .map_err(|_| crate::Error::UnknownTimeZone(timezone.to_string()))?;
+        Ok(Zoned::new(recurrence, timezone))
+    }
+}
+
+impl<D: RecurrentDay> Recurrence<D> {
+    /// Binds this recurrence to the time zone its times of day are wall times in.
+    pub fn in_timezone<Z: TimeZone>(self, timezone: Z) -> Zoned<D, Z> {
+        Zoned::new(self, timezone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Recurrence, Recurrent};
+    use chrono::{DateTime, FixedOffset, NaiveTime, TimeZone, Utc, Weekday};
+    use chrono_tz::Europe::Paris;
+
+    fn parse(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn zoned() {
+        let recurrence = Recurrence::new(
+            (Weekday::Mon, Weekday::Fri),
+            NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+        )
+        .unwrap()
+        .in_timezone(Paris);
+        // summer time
+        assert_eq!(
+            recurrence.next(&parse("2020-09-04T06:00:00Z")).unwrap(),
+            parse("2020-09-04T07:00:00Z")
+        );
+        // winter time
+        assert_eq!(
+            recurrence.next(&parse("2020-12-04T07:30:00Z")).unwrap(),
+            parse("2020-12-04T08:00:00Z")
+        );
+        assert_eq!(
+            recurrence.prev(&parse("2020-12-04T08:00:00Z")).unwrap(),
+            parse("2020-11-30T08:00:00Z")
+        );
+        // given back in the caller's zone
+        let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+        let next = recurrence
+            .next(&tokyo.with_ymd_and_hms(2020, 12, 4, 18, 0, 0).unwrap())
+            .unwrap();
+        assert_eq!(next.offset(), &tokyo);
+        assert_eq!(next, parse("2020-12-07T08:00:00Z"));
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    #[test]
+    fn named() {
+        let recurrence =
+            Recurrence::new(Weekday::Mon, NaiveTime::from_hms_opt(9, 0, 0).unwrap()).unwrap();
+        let zoned = crate::Zoned::named(recurrence.clone(), "Europe/Paris").unwrap();
+        assert_eq!(zoned.timezone(), &Paris);
+        assert!(matches!(
+            crate::Zoned::named(recurrence, "Europe/Nowhere"),
+            Err(crate::Error::UnknownTimeZone(_))
+        ));
+    }
+}